@@ -0,0 +1,834 @@
+//! Builds cypher queries clause by clause, quoting labels, types and property keys when they need
+//! it, and sending every value as a query parameter.
+//!
+//! ```
+//! use neo4jrs::builder::*;
+//! use neo4jrs::Query;
+//!
+//! let statement = match_node("p", "Person")
+//!     .where_(prop("p.age").gt(param(30)).and(prop("p.name").starts_with("A")))
+//!     .return_(["p"])
+//!     .order_by("p.name")
+//!     .limit(10);
+//! assert_eq!(
+//!     statement.to_string(),
+//!     "match (p:Person) where p.age > $p0 and p.name starts with $p1 return p order by p.name limit $p2"
+//! );
+//! assert_eq!(
+//!     statement.query(),
+//!     Query::new(&statement.to_string())
+//!         .param("p0", 30)
+//!         .param("p1", "A")
+//!         .param("p2", 10)
+//! );
+//! ```
+//!
+//! Strings given where a value is expected, e.g. `prop("p.name").eq("Alice")`, are values sent as
+//! parameters. Strings given where a name is expected, e.g. `return_(["p.name"])`, are variables
+//! or properties, see [`prop`].
+use crate::entity::Direction;
+use crate::types::{write_identifier, BoltMap, BoltType};
+use crate::Query;
+use std::fmt;
+
+/// A cypher expression, written into the query when it is added to a [`Statement`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr(Kind);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Variable(String),
+    Property(Box<Expr>, String),
+    Param(BoltType),
+    Function(String, Vec<Expr>),
+    Binary(Box<Expr>, &'static str, Box<Expr>),
+    Not(Box<Expr>),
+    /// `is null` and `is not null`
+    Postfix(Box<Expr>, &'static str),
+}
+
+const OR: u8 = 1;
+const XOR: u8 = 2;
+const AND: u8 = 3;
+const NOT: u8 = 4;
+const COMPARISON: u8 = 5;
+const ATOM: u8 = 6;
+
+/// A variable, e.g. `p`, or a property of a variable, e.g. `p.name`. Each part is quoted when it
+/// isn't a plain identifier, so `p.first name` becomes ``p.`first name` ``. Use [`Expr::prop`] for
+/// property keys containing a dot.
+pub fn prop(path: &str) -> Expr {
+    let mut parts = path.split('.');
+    let variable = var(parts.next().unwrap_or_default());
+    parts.fold(variable, Expr::prop)
+}
+
+/// A variable, quoted when it isn't a plain identifier
+pub fn var(name: &str) -> Expr {
+    Expr(Kind::Variable(name.to_owned()))
+}
+
+/// A value sent as a query parameter
+pub fn param(value: impl Into<BoltType>) -> Expr {
+    Expr(Kind::Param(value.into()))
+}
+
+/// A call to a function, e.g. `func("count", [var("p")])` or `func("apoc.text.join", ...)`
+pub fn func<I, E>(name: &str, args: I) -> Expr
+where
+    I: IntoIterator<Item = E>,
+    E: Into<Expr>,
+{
+    Expr(Kind::Function(
+        name.to_owned(),
+        args.into_iter().map(Into::into).collect(),
+    ))
+}
+
+impl Expr {
+    /// The property of this expression, the key is quoted when it isn't a plain identifier
+    pub fn prop(self, key: &str) -> Expr {
+        Expr(Kind::Property(Box::new(self), key.to_owned()))
+    }
+
+    pub fn eq(self, other: impl Into<Expr>) -> Expr {
+        self.binary("=", other)
+    }
+
+    pub fn ne(self, other: impl Into<Expr>) -> Expr {
+        self.binary("<>", other)
+    }
+
+    pub fn gt(self, other: impl Into<Expr>) -> Expr {
+        self.binary(">", other)
+    }
+
+    pub fn gte(self, other: impl Into<Expr>) -> Expr {
+        self.binary(">=", other)
+    }
+
+    pub fn lt(self, other: impl Into<Expr>) -> Expr {
+        self.binary("<", other)
+    }
+
+    pub fn lte(self, other: impl Into<Expr>) -> Expr {
+        self.binary("<=", other)
+    }
+
+    /// Whether the list contains this expression
+    pub fn in_(self, list: impl Into<Expr>) -> Expr {
+        self.binary("in", list)
+    }
+
+    pub fn starts_with(self, other: impl Into<Expr>) -> Expr {
+        self.binary("starts with", other)
+    }
+
+    pub fn ends_with(self, other: impl Into<Expr>) -> Expr {
+        self.binary("ends with", other)
+    }
+
+    pub fn contains(self, other: impl Into<Expr>) -> Expr {
+        self.binary("contains", other)
+    }
+
+    pub fn is_null(self) -> Expr {
+        Expr(Kind::Postfix(Box::new(self), "is null"))
+    }
+
+    pub fn is_not_null(self) -> Expr {
+        Expr(Kind::Postfix(Box::new(self), "is not null"))
+    }
+
+    pub fn and(self, other: impl Into<Expr>) -> Expr {
+        self.binary("and", other)
+    }
+
+    pub fn or(self, other: impl Into<Expr>) -> Expr {
+        self.binary("or", other)
+    }
+
+    pub fn xor(self, other: impl Into<Expr>) -> Expr {
+        self.binary("xor", other)
+    }
+
+    /// Names the expression in `return_` and `with`
+    pub fn as_(self, alias: &str) -> Projection {
+        Projection {
+            expr: self,
+            alias: Some(alias.to_owned()),
+        }
+    }
+
+    fn binary(self, operator: &'static str, other: impl Into<Expr>) -> Expr {
+        Expr(Kind::Binary(
+            Box::new(self),
+            operator,
+            Box::new(other.into()),
+        ))
+    }
+
+    fn precedence(&self) -> u8 {
+        match &self.0 {
+            Kind::Binary(_, "or", _) => OR,
+            Kind::Binary(_, "xor", _) => XOR,
+            Kind::Binary(_, "and", _) => AND,
+            Kind::Not(_) => NOT,
+            Kind::Binary(..) | Kind::Postfix(..) => COMPARISON,
+            _ => ATOM,
+        }
+    }
+
+    /// Writes the expression, adding its values to the parameters
+    fn write(&self, out: &mut String, params: &mut BoltMap) {
+        match &self.0 {
+            Kind::Variable(name) => write_identifier(out, name),
+            Kind::Property(expr, key) => {
+                expr.write_operand(out, params, ATOM);
+                out.push('.');
+                write_identifier(out, key);
+            }
+            Kind::Param(value) => {
+                let name = format!("p{}", params.len());
+                out.push('$');
+                out.push_str(&name);
+                params.put(name.into(), value.clone());
+            }
+            Kind::Function(name, args) => {
+                for (i, part) in name.split('.').enumerate() {
+                    if i > 0 {
+                        out.push('.');
+                    }
+                    write_identifier(out, part);
+                }
+                out.push('(');
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    arg.write(out, params);
+                }
+                out.push(')');
+            }
+            Kind::Binary(left, operator, right) => {
+                let precedence = self.precedence();
+                // Comparisons don't nest, `a = b = c` means `a = b and b = c`
+                let left_precedence = if precedence == COMPARISON {
+                    precedence + 1
+                } else {
+                    precedence
+                };
+                left.write_operand(out, params, left_precedence);
+                out.push(' ');
+                out.push_str(operator);
+                out.push(' ');
+                right.write_operand(out, params, precedence + 1);
+            }
+            Kind::Not(expr) => {
+                out.push_str("not ");
+                expr.write_operand(out, params, NOT);
+            }
+            Kind::Postfix(expr, operator) => {
+                expr.write_operand(out, params, ATOM);
+                out.push(' ');
+                out.push_str(operator);
+            }
+        }
+    }
+
+    /// Writes the expression, in parentheses when it binds less tightly than `precedence`
+    fn write_operand(&self, out: &mut String, params: &mut BoltMap, precedence: u8) {
+        if self.precedence() < precedence {
+            out.push('(');
+            self.write(out, params);
+            out.push(')');
+        } else {
+            self.write(out, params);
+        }
+    }
+}
+
+impl std::ops::Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Expr {
+        Expr(Kind::Not(Box::new(self)))
+    }
+}
+
+/// Values are sent as parameters
+impl<T: Into<BoltType>> From<T> for Expr {
+    fn from(value: T) -> Self {
+        param(value)
+    }
+}
+
+/// An item of `return_` or `with`, an expression with an optional alias. Strings are variables
+/// or properties, see [`prop`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    expr: Expr,
+    alias: Option<String>,
+}
+
+impl From<Expr> for Projection {
+    fn from(expr: Expr) -> Self {
+        Projection { expr, alias: None }
+    }
+}
+
+impl From<&str> for Projection {
+    fn from(path: &str) -> Self {
+        prop(path).into()
+    }
+}
+
+impl From<String> for Projection {
+    fn from(path: String) -> Self {
+        prop(&path).into()
+    }
+}
+
+/// A node of a pattern, e.g. `(p:Person {name: $p0})`
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodePattern {
+    variable: String,
+    labels: Vec<String>,
+    properties: Vec<(String, Expr)>,
+}
+
+/// A node bound to the variable, or an anonymous one when it is empty
+pub fn node(variable: &str) -> NodePattern {
+    NodePattern {
+        variable: variable.to_owned(),
+        labels: Vec::new(),
+        properties: Vec::new(),
+    }
+}
+
+impl NodePattern {
+    pub fn label(mut self, label: &str) -> Self {
+        self.labels.push(label.to_owned());
+        self
+    }
+
+    /// Matches, or creates, the node with the property set to the value
+    pub fn prop(mut self, key: &str, value: impl Into<Expr>) -> Self {
+        self.properties.push((key.to_owned(), value.into()));
+        self
+    }
+
+    /// `(a)-[r]->(b)`
+    pub fn out(self, relationship: RelationshipPattern, to: NodePattern) -> Pattern {
+        Pattern::from(self).out(relationship, to)
+    }
+
+    /// `(a)<-[r]-(b)`
+    pub fn in_(self, relationship: RelationshipPattern, from: NodePattern) -> Pattern {
+        Pattern::from(self).in_(relationship, from)
+    }
+
+    /// `(a)-[r]-(b)`, in either direction
+    pub fn both(self, relationship: RelationshipPattern, other: NodePattern) -> Pattern {
+        Pattern::from(self).both(relationship, other)
+    }
+
+    fn write(&self, out: &mut String, params: &mut BoltMap) {
+        out.push('(');
+        if !self.variable.is_empty() {
+            write_identifier(out, &self.variable);
+        }
+        for label in &self.labels {
+            out.push(':');
+            write_identifier(out, label);
+        }
+        write_properties(out, params, &self.properties);
+        out.push(')');
+    }
+}
+
+/// A relationship of a pattern, e.g. `[r:KNOWS {since: $p0}]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipPattern {
+    variable: String,
+    types: Vec<String>,
+    properties: Vec<(String, Expr)>,
+}
+
+/// A relationship of the type bound to the variable. The variable can be empty, and the type too
+/// for relationships of any type.
+pub fn rel(variable: &str, relationship_type: &str) -> RelationshipPattern {
+    let rel = RelationshipPattern {
+        variable: variable.to_owned(),
+        types: Vec::new(),
+        properties: Vec::new(),
+    };
+    if relationship_type.is_empty() {
+        rel
+    } else {
+        rel.or_type(relationship_type)
+    }
+}
+
+impl RelationshipPattern {
+    /// Also matches relationships of this type, e.g. `[r:KNOWS|LIKES]`
+    pub fn or_type(mut self, relationship_type: &str) -> Self {
+        self.types.push(relationship_type.to_owned());
+        self
+    }
+
+    pub fn prop(mut self, key: &str, value: impl Into<Expr>) -> Self {
+        self.properties.push((key.to_owned(), value.into()));
+        self
+    }
+
+    fn write(&self, out: &mut String, params: &mut BoltMap) {
+        out.push('[');
+        if !self.variable.is_empty() {
+            write_identifier(out, &self.variable);
+        }
+        for (i, relationship_type) in self.types.iter().enumerate() {
+            out.push(if i == 0 { ':' } else { '|' });
+            write_identifier(out, relationship_type);
+        }
+        write_properties(out, params, &self.properties);
+        out.push(']');
+    }
+}
+
+fn write_properties(out: &mut String, params: &mut BoltMap, properties: &[(String, Expr)]) {
+    if properties.is_empty() {
+        return;
+    }
+    out.push_str(" {");
+    for (i, (key, value)) in properties.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write_identifier(out, key);
+        out.push_str(": ");
+        value.write(out, params);
+    }
+    out.push('}');
+}
+
+/// A path of nodes and relationships, e.g. `(a:Person)-[:KNOWS]->(b:Person)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    start: NodePattern,
+    steps: Vec<(Option<Direction>, RelationshipPattern, NodePattern)>,
+}
+
+impl From<NodePattern> for Pattern {
+    fn from(start: NodePattern) -> Self {
+        Pattern {
+            start,
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl Pattern {
+    /// Continues the path with a relationship leaving the last node
+    pub fn out(mut self, relationship: RelationshipPattern, to: NodePattern) -> Self {
+        self.steps
+            .push((Some(Direction::Outgoing), relationship, to));
+        self
+    }
+
+    /// Continues the path with a relationship reaching the last node
+    pub fn in_(mut self, relationship: RelationshipPattern, from: NodePattern) -> Self {
+        self.steps
+            .push((Some(Direction::Incoming), relationship, from));
+        self
+    }
+
+    /// Continues the path with a relationship in either direction
+    pub fn both(mut self, relationship: RelationshipPattern, other: NodePattern) -> Self {
+        self.steps.push((None, relationship, other));
+        self
+    }
+
+    fn write(&self, out: &mut String, params: &mut BoltMap) {
+        self.start.write(out, params);
+        for (direction, relationship, node) in &self.steps {
+            out.push_str(match direction {
+                Some(Direction::Incoming) => "<-",
+                _ => "-",
+            });
+            relationship.write(out, params);
+            out.push_str(match direction {
+                Some(Direction::Outgoing) => "->",
+                _ => "-",
+            });
+            node.write(out, params);
+        }
+    }
+}
+
+/// A query built clause by clause, see the [module documentation](self).
+///
+/// Clauses are written in the order they are added, without checking that they make a valid
+/// query.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Statement {
+    text: String,
+    params: BoltMap,
+    /// The last clause, extended by the next `where_` or `order_by`
+    last: Last,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Last {
+    #[default]
+    Other,
+    Where,
+    OrderBy,
+}
+
+/// `match (variable:Label)`
+pub fn match_node(variable: &str, label: &str) -> Statement {
+    Statement::default().match_node(variable, label)
+}
+
+/// `optional match (variable:Label)`
+pub fn optional_match_node(variable: &str, label: &str) -> Statement {
+    Statement::default().optional_match_node(variable, label)
+}
+
+pub fn match_(pattern: impl Into<Pattern>) -> Statement {
+    Statement::default().match_(pattern)
+}
+
+pub fn optional_match(pattern: impl Into<Pattern>) -> Statement {
+    Statement::default().optional_match(pattern)
+}
+
+pub fn create(pattern: impl Into<Pattern>) -> Statement {
+    Statement::default().create(pattern)
+}
+
+pub fn merge(pattern: impl Into<Pattern>) -> Statement {
+    Statement::default().merge(pattern)
+}
+
+/// `unwind list as alias`
+pub fn unwind(list: impl Into<Expr>, alias: &str) -> Statement {
+    Statement::default().unwind(list, alias)
+}
+
+impl Statement {
+    pub fn match_node(self, variable: &str, label: &str) -> Self {
+        self.match_(node(variable).label(label))
+    }
+
+    pub fn optional_match_node(self, variable: &str, label: &str) -> Self {
+        self.optional_match(node(variable).label(label))
+    }
+
+    pub fn match_(self, pattern: impl Into<Pattern>) -> Self {
+        self.pattern("match ", pattern.into())
+    }
+
+    pub fn optional_match(self, pattern: impl Into<Pattern>) -> Self {
+        self.pattern("optional match ", pattern.into())
+    }
+
+    pub fn create(self, pattern: impl Into<Pattern>) -> Self {
+        self.pattern("create ", pattern.into())
+    }
+
+    pub fn merge(self, pattern: impl Into<Pattern>) -> Self {
+        self.pattern("merge ", pattern.into())
+    }
+
+    /// Filters the previous `match`, `optional_match` or `with`, consecutive conditions are
+    /// combined with `and`
+    pub fn where_(mut self, condition: impl Into<Expr>) -> Self {
+        if self.last == Last::Where {
+            self.text.push_str(" and ");
+            condition
+                .into()
+                .write_operand(&mut self.text, &mut self.params, AND + 1);
+        } else {
+            self = self.clause("where ");
+            condition
+                .into()
+                .write_operand(&mut self.text, &mut self.params, AND);
+            self.last = Last::Where;
+        }
+        self
+    }
+
+    /// `set target = value`, e.g. `set(prop("p.age"), 31)`
+    pub fn set(self, target: Expr, value: impl Into<Expr>) -> Self {
+        self.assignment("set ", target, value.into())
+    }
+
+    /// `on create set target = value`, after a `merge`
+    pub fn on_create_set(self, target: Expr, value: impl Into<Expr>) -> Self {
+        self.assignment("on create set ", target, value.into())
+    }
+
+    /// `on match set target = value`, after a `merge`
+    pub fn on_match_set(self, target: Expr, value: impl Into<Expr>) -> Self {
+        self.assignment("on match set ", target, value.into())
+    }
+
+    /// Deletes the nodes or relationships bound to the variables, a node which still has
+    /// relationships can't be deleted
+    pub fn delete<I>(self, variables: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.variables("delete ", variables)
+    }
+
+    /// Deletes the nodes bound to the variables along with their relationships
+    pub fn detach_delete<I>(self, variables: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.variables("detach delete ", variables)
+    }
+
+    pub fn with<I>(self, items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Projection>,
+    {
+        self.projections("with ", items)
+    }
+
+    pub fn unwind(mut self, list: impl Into<Expr>, alias: &str) -> Self {
+        self = self.clause("unwind ");
+        list.into()
+            .write_operand(&mut self.text, &mut self.params, ATOM);
+        self.text.push_str(" as ");
+        write_identifier(&mut self.text, alias);
+        self
+    }
+
+    pub fn return_<I>(self, items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Projection>,
+    {
+        self.projections("return ", items)
+    }
+
+    /// Sorts by the item, after the previous orders. An item with an alias sorts by the alias.
+    pub fn order_by(self, item: impl Into<Projection>) -> Self {
+        self.sort(item.into(), "")
+    }
+
+    pub fn order_by_desc(self, item: impl Into<Projection>) -> Self {
+        self.sort(item.into(), " desc")
+    }
+
+    pub fn skip(self, skip: usize) -> Self {
+        self.clause("skip ").value(param(skip as i64))
+    }
+
+    pub fn limit(self, limit: usize) -> Self {
+        self.clause("limit ").value(param(limit as i64))
+    }
+
+    /// The query, with the values of the statement as parameters
+    pub fn query(&self) -> Query {
+        Query::new_with_params(&self.text, self.params.clone())
+    }
+
+    fn clause(mut self, keyword: &str) -> Self {
+        if !self.text.is_empty() {
+            self.text.push(' ');
+        }
+        self.text.push_str(keyword);
+        self.last = Last::Other;
+        self
+    }
+
+    fn value(mut self, expr: Expr) -> Self {
+        expr.write(&mut self.text, &mut self.params);
+        self
+    }
+
+    fn pattern(mut self, keyword: &str, pattern: Pattern) -> Self {
+        self = self.clause(keyword);
+        pattern.write(&mut self.text, &mut self.params);
+        self
+    }
+
+    fn assignment(mut self, keyword: &str, target: Expr, value: Expr) -> Self {
+        self = self.clause(keyword);
+        target.write(&mut self.text, &mut self.params);
+        self.text.push_str(" = ");
+        self.value(value)
+    }
+
+    fn variables<I>(mut self, keyword: &str, variables: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self = self.clause(keyword);
+        for (i, variable) in variables.into_iter().enumerate() {
+            if i > 0 {
+                self.text.push_str(", ");
+            }
+            write_identifier(&mut self.text, variable.as_ref());
+        }
+        self
+    }
+
+    fn projections<I>(mut self, keyword: &str, items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Projection>,
+    {
+        self = self.clause(keyword);
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                self.text.push_str(", ");
+            }
+            let item = item.into();
+            item.expr.write(&mut self.text, &mut self.params);
+            if let Some(alias) = &item.alias {
+                self.text.push_str(" as ");
+                write_identifier(&mut self.text, alias);
+            }
+        }
+        self
+    }
+
+    fn sort(mut self, item: Projection, order: &str) -> Self {
+        if self.last == Last::OrderBy {
+            self.text.push_str(", ");
+        } else {
+            self = self.clause("order by ");
+            self.last = Last::OrderBy;
+        }
+        match &item.alias {
+            Some(alias) => write_identifier(&mut self.text, alias),
+            None => item.expr.write(&mut self.text, &mut self.params),
+        }
+        self.text.push_str(order);
+        self
+    }
+}
+
+/// Renders the query, values appear as parameters
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl From<Statement> for Query {
+    fn from(statement: Statement) -> Self {
+        Query::new_with_params(&statement.text, statement.params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_send_values_as_parameters() {
+        let statement = match_node("p", "Person")
+            .where_(prop("p.name").eq("Alice') detach delete p //"))
+            .where_(prop("p.age").gte(18).or(prop("p.guardian").is_not_null()))
+            .return_(["p"]);
+
+        assert_eq!(
+            statement.to_string(),
+            "match (p:Person) where p.name = $p0 and (p.age >= $p1 or p.guardian is not null) return p"
+        );
+        assert_eq!(
+            Query::from(statement.clone()),
+            Query::new(&statement.to_string())
+                .param("p0", "Alice') detach delete p //")
+                .param("p1", 18)
+        );
+    }
+
+    #[test]
+    fn should_quote_names_which_are_not_identifiers() {
+        let statement = match_(
+            node("a")
+                .label("Team Member")
+                .prop("first name", "Alice")
+                .out(
+                    rel("r", "WORKS`AT").or_type("MANAGES"),
+                    node("").label("Team"),
+                ),
+        )
+        .where_(!prop("a.first name").starts_with(var("x y")))
+        .return_(vec![
+            prop("a.first name").as_("first name"),
+            var("r").into(),
+        ])
+        .order_by_desc("a.first name")
+        .order_by_desc(func("count", [var("r")]).as_("total"));
+
+        assert_eq!(
+            statement.to_string(),
+            "match (a:`Team Member` {`first name`: $p0})-[r:`WORKS``AT`|MANAGES]->(:Team) where not a.`first name` starts with `x y` return a.`first name` as `first name`, r order by a.`first name` desc, total desc"
+        );
+    }
+
+    #[test]
+    fn should_write_updating_clauses() {
+        let statement = unwind(vec![1i64, 2], "id")
+            .merge(node("p").label("Person").prop("id", var("id")))
+            .on_create_set(prop("p.created"), func("datetime", Vec::<Expr>::new()))
+            .on_match_set(prop("p.visits"), prop("p.visits"))
+            .with(["p"])
+            .optional_match(node("p").in_(rel("", ""), node("o")))
+            .set(prop("o.seen"), true)
+            .detach_delete(["p"])
+            .delete(vec!["o".to_owned()]);
+
+        assert_eq!(
+            statement.to_string(),
+            "unwind $p0 as id merge (p:Person {id: id}) on create set p.created = datetime() on match set p.visits = p.visits with p optional match (p)<-[]-(o) set o.seen = $p1 detach delete p delete o"
+        );
+        assert_eq!(
+            statement.query(),
+            Query::new(&statement.to_string())
+                .param("p0", vec![1i64, 2])
+                .param("p1", true)
+        );
+    }
+
+    #[test]
+    fn should_parenthesize_by_precedence() {
+        let statement = Statement::default()
+            .where_(prop("a.x").eq(1).eq(true))
+            .where_((!prop("a.x").gt(1).and(prop("a.y").lt(2))).xor(var("b")));
+
+        assert_eq!(
+            statement.to_string(),
+            "where (a.x = $p0) = $p1 and (not (a.x > $p2 and a.y < $p3) xor b)"
+        );
+    }
+
+    #[test]
+    fn should_paginate_with_parameters() {
+        let statement = match_node("p", "Person")
+            .return_(["p.name"])
+            .order_by("p.name")
+            .skip(20)
+            .limit(10);
+
+        assert_eq!(
+            statement.query(),
+            Query::new("match (p:Person) return p.name order by p.name skip $p0 limit $p1")
+                .param("p0", 20)
+                .param("p1", 10)
+        );
+    }
+}
@@ -1,22 +1,124 @@
+#[cfg(feature = "json")]
+mod json;
+mod net;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "uuid")]
+pub use self::uuid::UuidBytes;
+
 use crate::errors::*;
 use crate::row::*;
 use crate::types::*;
 use secrecy::Secret;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::convert::{TryFrom, TryInto};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Converts every element of a list, failing on the first element that can't be converted.
+fn try_from_list<A: TryFrom<BoltType>, C: FromIterator<A>>(input: BoltType) -> Result<C> {
+    match input {
+        BoltType::List(l) => l
+            .value
+            .into_iter()
+            .map(|x| A::try_from(x).map_err(|_| Error::ConverstionError))
+            .collect(),
+        _ => Err(Error::ConverstionError),
+    }
+}
+
+/// Converts every value of a map, failing on the first value that can't be converted.
+fn try_from_map<A: TryFrom<BoltType>, C: FromIterator<(String, A)>>(input: BoltType) -> Result<C> {
+    match input {
+        BoltType::Map(m) => m
+            .value
+            .into_iter()
+            .map(|(k, v)| {
+                A::try_from(v)
+                    .map(|v| (k.value, v))
+                    .map_err(|_| Error::ConverstionError)
+            })
+            .collect(),
+        _ => Err(Error::ConverstionError),
+    }
+}
+
+/// Parses a string value, describing what was expected when the value isn't a string or can't be
+/// parsed.
+fn parse_string<T>(input: BoltType, expected: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match input {
+        BoltType::String(s) => s.value.parse().map_err(|e| {
+            Error::DeserializationError(format!("invalid {} `{}`: {}", expected, s.value, e))
+        }),
+        other => Err(Error::DeserializationError(format!(
+            "expected {} as a string, got {:?}",
+            expected, other
+        ))),
+    }
+}
 
 impl<A: TryFrom<BoltType>> TryFrom<BoltType> for Vec<A> {
     type Error = Error;
 
     fn try_from(input: BoltType) -> Result<Vec<A>> {
-        match input {
-            BoltType::List(l) => Ok(l
-                .value
-                .to_vec()
-                .iter()
-                .flat_map(|x| A::try_from(x.clone()))
-                .collect()),
-            _ => Err(Error::ConverstionError),
-        }
+        try_from_list(input)
+    }
+}
+
+impl<A: TryFrom<BoltType>> TryFrom<BoltType> for VecDeque<A> {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<VecDeque<A>> {
+        try_from_list(input)
+    }
+}
+
+impl<A: TryFrom<BoltType> + Eq + Hash> TryFrom<BoltType> for HashSet<A> {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<HashSet<A>> {
+        try_from_list(input)
+    }
+}
+
+impl<A: TryFrom<BoltType> + Ord> TryFrom<BoltType> for BTreeSet<A> {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<BTreeSet<A>> {
+        try_from_list(input)
+    }
+}
+
+impl<A: TryFrom<BoltType>, const N: usize> TryFrom<BoltType> for [A; N] {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<[A; N]> {
+        let list: Vec<A> = try_from_list(input)?;
+        list.try_into().map_err(|_| Error::ConverstionError)
+    }
+}
+
+impl<A: TryFrom<BoltType>> TryFrom<BoltType> for HashMap<String, A> {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<HashMap<String, A>> {
+        try_from_map(input)
+    }
+}
+
+impl<A: TryFrom<BoltType>> TryFrom<BoltType> for BTreeMap<String, A> {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<BTreeMap<String, A>> {
+        try_from_map(input)
     }
 }
 
@@ -143,6 +245,91 @@ impl TryFrom<BoltType> for (chrono::NaiveDateTime, String) {
     fn try_from(input: BoltType) -> Result<(chrono::NaiveDateTime, String)> {
         match input {
             BoltType::DateTimeZoneId(date_time_zone_id) => date_time_zone_id.try_into(),
+            #[cfg(feature = "tz")]
+            BoltType::DateTimeZoneIdUtc(date_time_zone_id) => {
+                let datetime: chrono::DateTime<chrono_tz::Tz> = date_time_zone_id.try_into()?;
+                Ok((
+                    datetime.naive_local(),
+                    datetime.timezone().name().to_owned(),
+                ))
+            }
+            // The local time of a datetime sent in UTC depends on the rules of its zone
+            #[cfg(not(feature = "tz"))]
+            BoltType::DateTimeZoneIdUtc(date_time_zone_id) => {
+                Err(Error::DeserializationError(format!(
+                    "the local time in `{}` of a datetime sent in UTC requires the `tz` feature",
+                    date_time_zone_id.tz_id.value
+                )))
+            }
+            _ => Err(Error::ConverstionError),
+        }
+    }
+}
+
+#[cfg(feature = "tz")]
+impl TryFrom<BoltType> for chrono::DateTime<chrono_tz::Tz> {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<chrono::DateTime<chrono_tz::Tz>> {
+        match input {
+            BoltType::DateTimeZoneId(date_time_zone_id) => date_time_zone_id.try_into(),
+            BoltType::DateTimeZoneIdUtc(date_time_zone_id) => date_time_zone_id.try_into(),
+            _ => Err(Error::ConverstionError),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<BoltType> for ::time::Date {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<::time::Date> {
+        match input {
+            BoltType::Date(d) => d.try_into_time(),
+            _ => Err(Error::ConverstionError),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<BoltType> for ::time::OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<::time::OffsetDateTime> {
+        match input {
+            BoltType::DateTime(d) => d.try_into_time(),
+            _ => Err(Error::ConverstionError),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<BoltType> for ::time::PrimitiveDateTime {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<::time::PrimitiveDateTime> {
+        match input {
+            BoltType::LocalDateTime(d) => d.try_into_time(),
+            _ => Err(Error::ConverstionError),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<BoltType> for (::time::Time, Option<::time::UtcOffset>) {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<(::time::Time, Option<::time::UtcOffset>)> {
+        match input {
+            BoltType::Time(bolt_time) => {
+                let (time, offset) = bolt_time.try_into_time()?;
+                if offset.is_utc() {
+                    Ok((time, None))
+                } else {
+                    Ok((time, Some(offset)))
+                }
+            }
+            BoltType::LocalTime(d) => Ok((d.try_into_time()?, None)),
             _ => Err(Error::ConverstionError),
         }
     }
@@ -224,6 +411,16 @@ impl TryFrom<BoltType> for BoltList {
     }
 }
 
+impl TryFrom<BoltType> for BoltMap {
+    type Error = Error;
+    fn try_from(input: BoltType) -> Result<BoltMap> {
+        match input {
+            BoltType::Map(m) => Ok(m),
+            _ => Err(Error::ConverstionError),
+        }
+    }
+}
+
 impl TryFrom<BoltType> for BoltString {
     type Error = Error;
     fn try_from(input: BoltType) -> Result<BoltString> {
@@ -286,7 +483,58 @@ impl Into<BoltType> for (chrono::NaiveDateTime, &str) {
     }
 }
 
-impl<A: Into<BoltType> + Clone> Into<BoltType> for Vec<A> {
+/// Sent with local seconds, the only encoding understood by the bolt versions the driver speaks
+#[cfg(feature = "tz")]
+impl Into<BoltType> for chrono::DateTime<chrono_tz::Tz> {
+    fn into(self) -> BoltType {
+        BoltType::DateTimeZoneId(self.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl Into<BoltType> for ::time::Date {
+    fn into(self) -> BoltType {
+        BoltType::Date(self.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl Into<BoltType> for ::time::Time {
+    fn into(self) -> BoltType {
+        BoltType::LocalTime(self.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl Into<BoltType> for ::time::PrimitiveDateTime {
+    fn into(self) -> BoltType {
+        BoltType::LocalDateTime(self.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl Into<BoltType> for ::time::OffsetDateTime {
+    fn into(self) -> BoltType {
+        BoltType::DateTime(self.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl Into<BoltType> for (::time::Time, ::time::UtcOffset) {
+    fn into(self) -> BoltType {
+        BoltType::Time(self.into())
+    }
+}
+
+impl<A: Into<BoltType>> Into<BoltType> for Vec<A> {
+    fn into(self) -> BoltType {
+        BoltType::List(BoltList {
+            value: self.into_iter().map(|v| v.into()).collect(),
+        })
+    }
+}
+
+impl<A: Into<BoltType> + Clone> Into<BoltType> for &[A] {
     fn into(self) -> BoltType {
         BoltType::List(BoltList {
             value: self.iter().map(|v| v.clone().into()).collect(),
@@ -294,12 +542,78 @@ impl<A: Into<BoltType> + Clone> Into<BoltType> for Vec<A> {
     }
 }
 
+impl<A: Into<BoltType>, const N: usize> Into<BoltType> for [A; N] {
+    fn into(self) -> BoltType {
+        BoltType::List(BoltList {
+            value: IntoIterator::into_iter(self).map(|v| v.into()).collect(),
+        })
+    }
+}
+
+impl<A: Into<BoltType>> Into<BoltType> for VecDeque<A> {
+    fn into(self) -> BoltType {
+        BoltType::List(BoltList {
+            value: self.into_iter().map(|v| v.into()).collect(),
+        })
+    }
+}
+
+impl<A: Into<BoltType>> Into<BoltType> for HashSet<A> {
+    fn into(self) -> BoltType {
+        BoltType::List(BoltList {
+            value: self.into_iter().map(|v| v.into()).collect(),
+        })
+    }
+}
+
+impl<A: Into<BoltType>> Into<BoltType> for BTreeSet<A> {
+    fn into(self) -> BoltType {
+        BoltType::List(BoltList {
+            value: self.into_iter().map(|v| v.into()).collect(),
+        })
+    }
+}
+
+impl<K: Into<BoltString>, V: Into<BoltType>> Into<BoltType> for HashMap<K, V> {
+    fn into(self) -> BoltType {
+        BoltType::Map(
+            self.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<K: Into<BoltString>, V: Into<BoltType>> Into<BoltType> for BTreeMap<K, V> {
+    fn into(self) -> BoltType {
+        BoltType::Map(
+            self.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+// `Box` is a fundamental type, so the conversion has to be implemented on `BoltType`.
+impl<T: Into<BoltType>> From<Box<T>> for BoltType {
+    fn from(value: Box<T>) -> BoltType {
+        (*value).into()
+    }
+}
+
+impl<T: Into<BoltType> + Clone> Into<BoltType> for Arc<T> {
+    fn into(self) -> BoltType {
+        Arc::try_unwrap(self)
+            .unwrap_or_else(|shared| (*shared).clone())
+            .into()
+    }
+}
+
 impl<A: Into<BoltType> + Clone> Into<BoltType> for Option<A> {
     fn into(self) -> BoltType {
-        if self.is_none() {
-            BoltType::Null(BoltNull::default())
-        } else {
-            self.unwrap().into()
+        match self {
+            Some(value) => value.into(),
+            None => BoltType::Null(BoltNull),
         }
     }
 }
@@ -347,3 +661,125 @@ impl Into<BoltType> for bool {
         BoltType::Boolean(BoltBoolean::new(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_hash_map_into_bolt_map() {
+        let mut props = HashMap::new();
+        props.insert("name", "Mark");
+        props.insert("city", "Paris");
+
+        let value: BoltType = props.into();
+
+        match value {
+            BoltType::Map(map) => {
+                assert_eq!(map.len(), 2);
+                assert_eq!(map.get::<String>("name").unwrap(), "Mark");
+                assert_eq!(map.get::<String>("city").unwrap(), "Paris");
+            }
+            _ => unreachable!("expected a map"),
+        }
+    }
+
+    #[test]
+    fn should_convert_bolt_map_into_hash_map_and_btree_map() {
+        let map: BoltMap = vec![("a".into(), 1.into()), ("b".into(), 2.into())]
+            .into_iter()
+            .collect();
+
+        let hash_map: HashMap<String, i64> = BoltType::Map(map.clone()).try_into().unwrap();
+        let btree_map: BTreeMap<String, i64> = BoltType::Map(map).try_into().unwrap();
+
+        assert_eq!(hash_map.get("a"), Some(&1));
+        assert_eq!(hash_map.get("b"), Some(&2));
+        assert_eq!(
+            btree_map.into_iter().collect::<Vec<_>>(),
+            vec![("a".to_owned(), 1), ("b".to_owned(), 2)]
+        );
+    }
+
+    #[test]
+    fn should_convert_nested_collections() {
+        let mut props = BTreeMap::new();
+        props.insert("tags".to_owned(), vec!["a", "b"]);
+        props.insert("empty".to_owned(), vec![]);
+
+        let value: BoltType = props.into();
+        let converted: HashMap<String, Vec<String>> = value.try_into().unwrap();
+
+        assert_eq!(converted["tags"], vec!["a".to_owned(), "b".to_owned()]);
+        assert!(converted["empty"].is_empty());
+    }
+
+    #[test]
+    fn should_convert_sets_slices_arrays_and_pointers_into_lists() {
+        let expected = BoltType::List(vec![1.into(), 2.into()].into());
+
+        let set: BTreeSet<i64> = vec![2, 1].into_iter().collect();
+        let deque: VecDeque<i64> = vec![1, 2].into_iter().collect();
+        let slice: &[i64] = &[1, 2];
+
+        assert_eq!(Into::<BoltType>::into(set), expected);
+        assert_eq!(Into::<BoltType>::into(deque), expected);
+        assert_eq!(Into::<BoltType>::into(slice), expected);
+        assert_eq!(Into::<BoltType>::into([1i64, 2]), expected);
+        assert_eq!(BoltType::from(Box::new(vec![1i64, 2])), expected);
+        assert_eq!(Into::<BoltType>::into(Arc::new(vec![1i64, 2])), expected);
+
+        let hash_set: HashSet<i64> = vec![1, 2].into_iter().collect();
+        match Into::<BoltType>::into(hash_set) {
+            BoltType::List(list) => assert_eq!(list.len(), 2),
+            _ => unreachable!("expected a list"),
+        }
+    }
+
+    #[test]
+    fn should_convert_lists_into_sets_deques_and_arrays() {
+        let list = BoltType::List(vec![1.into(), 2.into(), 2.into()].into());
+
+        let hash_set: HashSet<i64> = list.clone().try_into().unwrap();
+        let btree_set: BTreeSet<i64> = list.clone().try_into().unwrap();
+        let deque: VecDeque<i64> = list.clone().try_into().unwrap();
+        let array: [i64; 3] = list.clone().try_into().unwrap();
+
+        assert_eq!(hash_set.len(), 2);
+        assert_eq!(btree_set.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(deque, vec![1, 2, 2]);
+        assert_eq!(array, [1, 2, 2]);
+        assert!(TryInto::<[i64; 2]>::try_into(list).is_err());
+    }
+
+    #[test]
+    fn should_fail_when_an_element_can_not_be_converted() {
+        let list = BoltType::List(vec![1.into(), "two".into()].into());
+        let map = BoltType::Map(
+            vec![("a".into(), 1.into()), ("b".into(), "two".into())]
+                .into_iter()
+                .collect(),
+        );
+
+        assert!(TryInto::<Vec<i64>>::try_into(list).is_err());
+        assert!(TryInto::<HashMap<String, i64>>::try_into(map).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "tz"))]
+    fn should_require_tz_for_the_local_time_of_a_utc_datetime_with_zone_id() {
+        let datetime = BoltType::DateTimeZoneIdUtc(BoltDateTimeZoneIdUtc {
+            seconds: 0.into(),
+            nanoseconds: 0.into(),
+            tz_id: "Europe/Paris".into(),
+        });
+
+        match TryInto::<(chrono::NaiveDateTime, String)>::try_into(datetime) {
+            Err(Error::DeserializationError(message)) => assert_eq!(
+                message,
+                "the local time in `Europe/Paris` of a datetime sent in UTC requires the `tz` feature"
+            ),
+            other => panic!("expected a deserialization error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Conversions between [`serde_json::Value`] and bolt types, enabled with the `json` feature.
+//!
+//! Bolt values are mapped to json as follows:
+//!
+//! * null, booleans, integers, floats, strings, lists and maps map to their json counterpart,
+//!   non finite floats become `null`
+//! * bytes become an array of numbers
+//! * dates, times, datetimes and durations become ISO-8601 strings, a datetime with a zone id is
+//!   rendered as `2015-07-01T08:59:60.123[Europe/Paris]`
+//! * points become GeoJSON points with an extra `srid` member, e.g.
+//!   `{"type": "Point", "coordinates": [1.0, 2.0], "srid": 7203}`
+//! * nodes become `{"id", "labels", "properties"}`
+//! * relationships become `{"id", "type", "start_node_id", "end_node_id", "properties"}`, the
+//!   start and end node ids are omitted for relationships within a path
+//! * paths become `{"nodes", "relationships"}`
+//!
+//! Converting json into bolt is structural: strings stay strings, numbers become an integer when
+//! they fit in an `i64` and a float otherwise. The only exception are GeoJSON points, which are
+//! converted to a bolt point (with an srid of 4326 or 4979 unless `srid` is given) as maps can't
+//! be stored as node properties anyway.
+use crate::errors::*;
+use crate::types::*;
+use crate::Crs;
+use serde_json::{json, Map, Number, Value};
+use std::convert::{TryFrom, TryInto};
+
+impl TryFrom<BoltType> for Value {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<Value> {
+        let value = match input {
+            BoltType::Null(_) => Value::Null,
+            BoltType::Boolean(b) => Value::Bool(b.value),
+            BoltType::Integer(i) => Value::Number(i.value.into()),
+            BoltType::Float(f) => Number::from_f64(f.value)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+            BoltType::String(s) => Value::String(s.value),
+            BoltType::Bytes(b) => Value::Array(b.value.iter().map(|&b| b.into()).collect()),
+            BoltType::List(l) => Value::Array(
+                l.into_iter()
+                    .map(Value::try_from)
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            BoltType::Map(m) => m.try_into()?,
+            BoltType::Node(n) => json!({
+                "id": n.id.value,
+                "labels": Value::try_from(BoltType::List(n.labels))?,
+                "properties": Value::try_from(n.properties)?,
+            }),
+            BoltType::Relation(r) => json!({
+                "id": r.id.value,
+                "type": r.typ.value,
+                "start_node_id": r.start_node_id.value,
+                "end_node_id": r.end_node_id.value,
+                "properties": Value::try_from(r.properties)?,
+            }),
+            BoltType::UnboundedRelation(r) => json!({
+                "id": r.id.value,
+                "type": r.typ.value,
+                "properties": Value::try_from(r.properties)?,
+            }),
+            BoltType::Path(p) => json!({
+                "nodes": Value::try_from(BoltType::List(p.nodes))?,
+                "relationships": Value::try_from(BoltType::List(p.rels))?,
+            }),
+            BoltType::Point2D(p) => json!({
+                "type": "Point",
+                "coordinates": [p.x.value, p.y.value],
+                "srid": p.sr_id.value,
+            }),
+            BoltType::Point3D(p) => json!({
+                "type": "Point",
+                "coordinates": [p.x.value, p.y.value, p.z.value],
+                "srid": p.sr_id.value,
+            }),
+            temporal => Value::String(iso_8601(&temporal)?),
+        };
+        Ok(value)
+    }
+}
+
+impl TryFrom<BoltMap> for Value {
+    type Error = Error;
+
+    fn try_from(input: BoltMap) -> Result<Value> {
+        let mut map = Map::with_capacity(input.len());
+        for (key, value) in input.value {
+            map.insert(key.value, value.try_into()?);
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+impl Into<BoltType> for Value {
+    fn into(self) -> BoltType {
+        match self {
+            Value::Null => BoltType::Null(BoltNull),
+            Value::Bool(b) => BoltType::Boolean(BoltBoolean::new(b)),
+            Value::Number(n) => match n.as_i64() {
+                Some(i) => BoltType::Integer(BoltInteger::new(i)),
+                None => BoltType::Float(BoltFloat::new(n.as_f64().unwrap_or(f64::NAN))),
+            },
+            Value::String(s) => BoltType::String(s.into()),
+            Value::Array(a) => {
+                BoltType::List(a.into_iter().map(Value::into).collect::<Vec<_>>().into())
+            }
+            Value::Object(o) => match parse_point(&o) {
+                Some(point) => point,
+                None => BoltType::Map(object_into_map(o)),
+            },
+        }
+    }
+}
+
+impl TryFrom<Value> for BoltMap {
+    type Error = Error;
+
+    fn try_from(input: Value) -> Result<BoltMap> {
+        match input {
+            Value::Object(o) => Ok(object_into_map(o)),
+            _ => Err(Error::ConverstionError),
+        }
+    }
+}
+
+fn object_into_map(object: Map<String, Value>) -> BoltMap {
+    object
+        .into_iter()
+        .map(|(k, v)| (k.into(), v.into()))
+        .collect()
+}
+
+fn parse_point(object: &Map<String, Value>) -> Option<BoltType> {
+    if object.get("type")?.as_str()? != "Point" {
+        return None;
+    }
+    let coordinates = object
+        .get("coordinates")?
+        .as_array()?
+        .iter()
+        .map(Value::as_f64)
+        .collect::<Option<Vec<f64>>>()?;
+    let sr_id = match object.get("srid") {
+        Some(srid) => Some(srid.as_i64()?),
+        None => None,
+    };
+
+    match coordinates[..] {
+        [x, y] => Some(BoltType::Point2D(BoltPoint2D {
+            sr_id: sr_id.unwrap_or_else(|| Crs::Wgs84.srid()).into(),
+            x: BoltFloat::new(x),
+            y: BoltFloat::new(y),
+        })),
+        [x, y, z] => Some(BoltType::Point3D(BoltPoint3D {
+            sr_id: sr_id.unwrap_or_else(|| Crs::Wgs843D.srid()).into(),
+            x: BoltFloat::new(x),
+            y: BoltFloat::new(y),
+            z: BoltFloat::new(z),
+        })),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::row::Row;
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
+
+    fn to_json<T: Into<BoltType>>(value: T) -> Value {
+        Value::try_from(value.into()).unwrap()
+    }
+
+    #[test]
+    fn should_convert_scalars_into_json() {
+        assert_eq!(to_json(BoltType::Null(BoltNull)), Value::Null);
+        assert_eq!(to_json(true), json!(true));
+        assert_eq!(to_json(42i64), json!(42));
+        assert_eq!(to_json(1.5), json!(1.5));
+        assert_eq!(to_json(f64::NAN), Value::Null);
+        assert_eq!(to_json("a"), json!("a"));
+        assert_eq!(to_json(vec![1u8, 2]), json!([1, 2]));
+    }
+
+    #[test]
+    fn should_convert_graph_entities_into_json() {
+        let node = BoltNode::new(
+            42.into(),
+            vec!["Person".into()].into(),
+            vec![("name".into(), "Mark".into())].into_iter().collect(),
+        );
+        let relation = BoltRelation {
+            id: 1.into(),
+            start_node_id: 42.into(),
+            end_node_id: 43.into(),
+            typ: "KNOWS".into(),
+            properties: BoltMap::default(),
+        };
+
+        assert_eq!(
+            to_json(node),
+            json!({"id": 42, "labels": ["Person"], "properties": {"name": "Mark"}})
+        );
+        assert_eq!(
+            to_json(relation),
+            json!({"id": 1, "type": "KNOWS", "start_node_id": 42, "end_node_id": 43, "properties": {}})
+        );
+    }
+
+    #[test]
+    fn should_convert_temporal_values_into_iso_strings() {
+        let date = NaiveDate::from_ymd_opt(2010, 1, 1).unwrap();
+        let time = NaiveTime::from_hms_nano_opt(7, 8, 9, 100).unwrap();
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let datetime = DateTime::parse_from_rfc3339("2015-06-24T12:50:35.556+01:00").unwrap();
+
+        assert_eq!(to_json(date), json!("2010-01-01"));
+        assert_eq!(to_json(time), json!("07:08:09.000000100"));
+        assert_eq!(to_json((time, offset)), json!("07:08:09.000000100+02:00"));
+        assert_eq!(to_json(datetime), json!("2015-06-24T12:50:35.556+01:00"));
+        assert_eq!(
+            to_json(date.and_time(time)),
+            json!("2010-01-01T07:08:09.000000100")
+        );
+        assert_eq!(
+            to_json((date.and_time(time), "Europe/Paris")),
+            json!("2010-01-01T07:08:09.000000100[Europe/Paris]")
+        );
+    }
+
+    #[test]
+    fn should_convert_points_to_and_from_geo_json() {
+        let point = BoltType::Point2D(BoltPoint2D {
+            sr_id: 7203.into(),
+            x: BoltFloat::new(1.0),
+            y: BoltFloat::new(2.0),
+        });
+        let geo_json = json!({"type": "Point", "coordinates": [1.0, 2.0], "srid": 7203});
+
+        assert_eq!(to_json(point.clone()), geo_json);
+        assert_eq!(Into::<BoltType>::into(geo_json), point);
+
+        match json!({"type": "Point", "coordinates": [1.0, 2.0, 3.0]}).into() {
+            BoltType::Point3D(p) => assert_eq!(p.sr_id.value, 4979),
+            _ => unreachable!("expected a 3d point"),
+        }
+    }
+
+    #[test]
+    fn should_convert_json_into_bolt_types() {
+        let value: BoltType = json!({
+            "name": "Mark",
+            "age": 42,
+            "score": 1.5,
+            "tags": ["a", "b"],
+            "manager": null,
+        })
+        .into();
+
+        let map: BoltMap = value.try_into().unwrap();
+        assert_eq!(map.get::<String>("name").unwrap(), "Mark");
+        assert_eq!(map.get::<i64>("age").unwrap(), 42);
+        assert_eq!(map.get::<f64>("score").unwrap(), 1.5);
+        assert_eq!(map.get::<Vec<String>>("tags").unwrap(), vec!["a", "b"]);
+        assert!(BoltMap::try_from(json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn should_round_trip_maps_through_json() {
+        let map = BoltMap::try_from(json!({"a": 1, "b": [true, "c"], "d": {"e": 2.5}})).unwrap();
+
+        let value = Value::try_from(map.clone()).unwrap();
+
+        assert_eq!(BoltMap::try_from(value).unwrap(), map);
+    }
+
+    #[test]
+    fn should_convert_row_into_json() {
+        let fields: BoltList = vec!["n".into(), "count".into()].into();
+        let data: BoltList = vec!["Mark".into(), 3.into()].into();
+        let row = Row::new(fields, data);
+
+        assert_eq!(row.to_json().unwrap(), json!({"n": "Mark", "count": 3}));
+        assert_eq!(row.get::<Value>("count").unwrap(), json!(3));
+    }
+
+    #[test]
+    fn should_convert_json_into_row() {
+        let row = Row::try_from(json!({"name": "Mark", "age": 42})).unwrap();
+
+        assert_eq!(row.get::<String>("name").unwrap(), "Mark");
+        assert_eq!(row.get::<i64>("age").unwrap(), 42);
+        assert!(Row::try_from(json!("Mark")).is_err());
+    }
+}
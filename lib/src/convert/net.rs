@@ -0,0 +1,97 @@
+//! Conversions for network addresses, sent and read as strings such as `127.0.0.1`,
+//! `[::1]:7687` or, with the `url` feature, `https://neo4j.com/`.
+
+use super::parse_string;
+use crate::errors::*;
+use crate::types::*;
+use std::convert::TryFrom;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+macro_rules! string_conversions {
+    ($($t:ty => $expected:literal),* $(,)?) => {
+        $(
+            impl Into<BoltType> for $t {
+                fn into(self) -> BoltType {
+                    BoltType::String(self.to_string().into())
+                }
+            }
+
+            impl TryFrom<BoltType> for $t {
+                type Error = Error;
+
+                fn try_from(input: BoltType) -> Result<$t> {
+                    parse_string(input, $expected)
+                }
+            }
+        )*
+    };
+}
+
+string_conversions! {
+    IpAddr => "ip address",
+    Ipv4Addr => "ipv4 address",
+    Ipv6Addr => "ipv6 address",
+    SocketAddr => "socket address",
+}
+
+#[cfg(feature = "url")]
+string_conversions! {
+    url::Url => "url",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_ip_addresses() {
+        let ip: IpAddr = "2001:db8::1".parse().unwrap();
+
+        let bolt: BoltType = ip.into();
+
+        assert_eq!(bolt, BoltType::String("2001:db8::1".into()));
+        assert_eq!(IpAddr::try_from(bolt).unwrap(), ip);
+        assert_eq!(
+            Ipv4Addr::try_from(BoltType::String("10.0.0.1".into())).unwrap(),
+            Ipv4Addr::new(10, 0, 0, 1)
+        );
+    }
+
+    #[test]
+    fn should_convert_socket_addresses() {
+        let addr: SocketAddr = "[::1]:7687".parse().unwrap();
+
+        let bolt: BoltType = addr.into();
+
+        assert_eq!(bolt, BoltType::String("[::1]:7687".into()));
+        assert_eq!(SocketAddr::try_from(bolt).unwrap(), addr);
+    }
+
+    #[test]
+    fn should_describe_invalid_values() {
+        let error = IpAddr::try_from(BoltType::String("localhost".into())).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::DeserializationError(message)
+                if message == "invalid ip address `localhost`: invalid IP address syntax"
+        ));
+
+        let error = SocketAddr::try_from(BoltType::Integer(42.into())).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::DeserializationError(message) if message.starts_with("expected socket address as a string")
+        ));
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn should_convert_urls() {
+        let url = url::Url::parse("https://neo4j.com/docs?q=1").unwrap();
+
+        let bolt: BoltType = url.clone().into();
+
+        assert_eq!(bolt, BoltType::String("https://neo4j.com/docs?q=1".into()));
+        assert_eq!(url::Url::try_from(bolt).unwrap(), url);
+        assert!(url::Url::try_from(BoltType::String("not a url".into())).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+//! Conversions for [`uuid::Uuid`], enabled with the `uuid` feature.
+//!
+//! Uuids are sent in their hyphenated string form, which is what `randomUUID()` returns in
+//! cypher. Wrap them in [`UuidBytes`] to send the 16 raw bytes instead; both forms are accepted
+//! when reading a uuid back.
+
+use super::parse_string;
+use crate::errors::*;
+use crate::types::*;
+use std::convert::TryFrom;
+
+/// A uuid sent as 16 raw bytes rather than as a string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UuidBytes(pub ::uuid::Uuid);
+
+impl Into<BoltType> for ::uuid::Uuid {
+    fn into(self) -> BoltType {
+        BoltType::String(self.hyphenated().to_string().into())
+    }
+}
+
+impl Into<BoltType> for UuidBytes {
+    fn into(self) -> BoltType {
+        BoltType::Bytes(BoltBytes::new(self.0.as_bytes().to_vec().into()))
+    }
+}
+
+impl TryFrom<BoltType> for ::uuid::Uuid {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<::uuid::Uuid> {
+        match input {
+            BoltType::Bytes(b) => ::uuid::Uuid::from_slice(&b.value).map_err(|e| {
+                Error::DeserializationError(format!("invalid uuid bytes {:?}: {}", b.value, e))
+            }),
+            input => parse_string(input, "uuid"),
+        }
+    }
+}
+
+impl TryFrom<BoltType> for UuidBytes {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<UuidBytes> {
+        ::uuid::Uuid::try_from(input).map(UuidBytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::uuid::Uuid;
+
+    const UUID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+    #[test]
+    fn should_convert_uuids_as_strings() {
+        let uuid = Uuid::parse_str(UUID).unwrap();
+
+        let bolt: BoltType = uuid.into();
+
+        assert_eq!(bolt, BoltType::String(UUID.into()));
+        assert_eq!(Uuid::try_from(bolt).unwrap(), uuid);
+    }
+
+    #[test]
+    fn should_convert_uuids_as_bytes() {
+        let uuid = Uuid::parse_str(UUID).unwrap();
+
+        let bolt: BoltType = UuidBytes(uuid).into();
+
+        assert_eq!(
+            bolt,
+            BoltType::Bytes(BoltBytes::new(uuid.as_bytes().to_vec().into()))
+        );
+        assert_eq!(Uuid::try_from(bolt.clone()).unwrap(), uuid);
+        assert_eq!(UuidBytes::try_from(bolt).unwrap(), UuidBytes(uuid));
+    }
+
+    #[test]
+    fn should_describe_invalid_uuids() {
+        let error = Uuid::try_from(BoltType::String("not-a-uuid".into())).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::DeserializationError(message) if message.starts_with("invalid uuid `not-a-uuid`: ")
+        ));
+
+        let bytes = BoltType::Bytes(BoltBytes::new(vec![1, 2, 3].into()));
+        assert!(matches!(
+            Uuid::try_from(bytes),
+            Err(Error::DeserializationError(message)) if message.starts_with("invalid uuid bytes")
+        ));
+    }
+}
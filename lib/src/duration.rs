@@ -0,0 +1,707 @@
+use crate::errors::*;
+use crate::types::*;
+use chrono::{DateTime, Days, Months, NaiveDate, NaiveDateTime, TimeDelta, TimeZone};
+use std::convert::{TryFrom, TryInto};
+use std::fmt::{self, Display};
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_DAY: i128 = 86_400 * NANOS_PER_SECOND;
+
+/// A cypher duration, made of months, days, seconds and nanoseconds.
+///
+/// Unlike [`std::time::Duration`] the components don't collapse into a fixed length of time, a
+/// month or a day only gets a length once the duration is applied to a date, and any of them can
+/// be negative. The nanoseconds are normalized to `0..1_000_000_000`, carrying into the seconds.
+///
+/// A duration can be parsed from and formatted as an ISO-8601 string:
+///
+/// ```
+/// use neo4jrs::Duration;
+///
+/// let duration: Duration = "P1Y2M3DT4H5M6.7S".parse().unwrap();
+/// assert_eq!(duration.months(), 14);
+/// assert_eq!(duration.days(), 3);
+/// assert_eq!(duration.seconds(), 4 * 3600 + 5 * 60 + 6);
+/// assert_eq!(duration.nanoseconds(), 700_000_000);
+/// assert_eq!(duration.to_string(), "P1Y2M3DT4H5M6.7S");
+/// ```
+///
+/// It can be added to (or subtracted from) chrono dates using cypher semantics: months are added
+/// first, clamping the day to the end of the month, then days and finally the seconds.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use neo4jrs::Duration;
+///
+/// let date = NaiveDate::from_ymd_opt(2021, 1, 31).unwrap();
+/// let duration = Duration::new(1, 1, 0, 0);
+/// assert_eq!(date + duration, NaiveDate::from_ymd_opt(2021, 3, 1).unwrap());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Duration {
+    months: i64,
+    days: i64,
+    seconds: i64,
+    nanoseconds: i32,
+}
+
+impl Duration {
+    /// Creates a duration, nanoseconds outside of `0..1_000_000_000` are carried into the seconds
+    ///
+    /// # Panics
+    ///
+    /// Panics if the seconds overflow once the nanoseconds are carried.
+    pub fn new(months: i64, days: i64, seconds: i64, nanoseconds: i64) -> Self {
+        Self::checked_new(months, days, seconds, nanoseconds).expect("duration seconds overflow")
+    }
+
+    /// Creates a duration, returns `None` if the seconds overflow once the nanoseconds are carried
+    pub fn checked_new(months: i64, days: i64, seconds: i64, nanoseconds: i64) -> Option<Self> {
+        let seconds = seconds.checked_add(nanoseconds.div_euclid(NANOS_PER_SECOND as i64))?;
+        let nanoseconds = nanoseconds.rem_euclid(NANOS_PER_SECOND as i64) as i32;
+        Some(Duration {
+            months,
+            days,
+            seconds,
+            nanoseconds,
+        })
+    }
+
+    pub fn months(&self) -> i64 {
+        self.months
+    }
+
+    pub fn days(&self) -> i64 {
+        self.days
+    }
+
+    pub fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// Always within `0..1_000_000_000`
+    pub fn nanoseconds(&self) -> i32 {
+        self.nanoseconds
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Duration::default()
+    }
+
+    pub fn checked_add(&self, rhs: Duration) -> Option<Duration> {
+        Self::checked_new(
+            self.months.checked_add(rhs.months)?,
+            self.days.checked_add(rhs.days)?,
+            self.seconds.checked_add(rhs.seconds)?,
+            self.nanoseconds as i64 + rhs.nanoseconds as i64,
+        )
+    }
+
+    pub fn checked_sub(&self, rhs: Duration) -> Option<Duration> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_neg(&self) -> Option<Duration> {
+        Self::checked_new(
+            self.months.checked_neg()?,
+            self.days.checked_neg()?,
+            self.seconds.checked_neg()?,
+            -(self.nanoseconds as i64),
+        )
+    }
+
+    /// Adds the duration to a date, the seconds are added as whole days (truncated towards zero)
+    pub fn checked_add_to_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let date = add_months(
+            date,
+            self.months,
+            NaiveDate::checked_add_months,
+            NaiveDate::checked_sub_months,
+        )?;
+        let days = self
+            .days
+            .checked_add((self.total_nanos() / NANOS_PER_DAY) as i64)?;
+        add_days(
+            date,
+            days,
+            NaiveDate::checked_add_days,
+            NaiveDate::checked_sub_days,
+        )
+    }
+
+    pub fn checked_add_to_naive_datetime(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        let datetime = add_months(
+            datetime,
+            self.months,
+            NaiveDateTime::checked_add_months,
+            NaiveDateTime::checked_sub_months,
+        )?;
+        let datetime = add_days(
+            datetime,
+            self.days,
+            NaiveDateTime::checked_add_days,
+            NaiveDateTime::checked_sub_days,
+        )?;
+        datetime.checked_add_signed(self.time_delta()?)
+    }
+
+    /// Adds the duration to a datetime, months and days are added to the local date so that
+    /// `P1D` keeps the local time across a daylight saving transition, while the seconds are
+    /// added as an exact amount of time.
+    ///
+    /// Returns `None` if the local datetime, after adding months and days, doesn't exist or is
+    /// ambiguous in the time zone.
+    pub fn checked_add_to_datetime<Tz: TimeZone>(
+        &self,
+        datetime: DateTime<Tz>,
+    ) -> Option<DateTime<Tz>> {
+        let datetime = add_months(
+            datetime,
+            self.months,
+            DateTime::checked_add_months,
+            DateTime::checked_sub_months,
+        )?;
+        let datetime = add_days(
+            datetime,
+            self.days,
+            DateTime::checked_add_days,
+            DateTime::checked_sub_days,
+        )?;
+        datetime.checked_add_signed(self.time_delta()?)
+    }
+
+    fn total_nanos(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SECOND + self.nanoseconds as i128
+    }
+
+    fn time_delta(&self) -> Option<TimeDelta> {
+        TimeDelta::new(self.seconds, self.nanoseconds as u32)
+    }
+}
+
+fn add_months<T>(
+    value: T,
+    months: i64,
+    add: impl Fn(T, Months) -> Option<T>,
+    sub: impl Fn(T, Months) -> Option<T>,
+) -> Option<T> {
+    let abs = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if months < 0 {
+        sub(value, abs)
+    } else {
+        add(value, abs)
+    }
+}
+
+fn add_days<T>(
+    value: T,
+    days: i64,
+    add: impl Fn(T, Days) -> Option<T>,
+    sub: impl Fn(T, Days) -> Option<T>,
+) -> Option<T> {
+    let abs = Days::new(days.unsigned_abs());
+    if days < 0 {
+        sub(value, abs)
+    } else {
+        add(value, abs)
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, rhs: Duration) -> Duration {
+        self.checked_add(rhs)
+            .expect("`Duration + Duration` overflowed")
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+
+    fn sub(self, rhs: Duration) -> Duration {
+        self.checked_sub(rhs)
+            .expect("`Duration - Duration` overflowed")
+    }
+}
+
+impl Neg for Duration {
+    type Output = Duration;
+
+    fn neg(self) -> Duration {
+        self.checked_neg().expect("`-Duration` overflowed")
+    }
+}
+
+impl Add<Duration> for NaiveDate {
+    type Output = NaiveDate;
+
+    fn add(self, rhs: Duration) -> NaiveDate {
+        rhs.checked_add_to_date(self)
+            .expect("`NaiveDate + Duration` overflowed")
+    }
+}
+
+impl Sub<Duration> for NaiveDate {
+    type Output = NaiveDate;
+
+    fn sub(self, rhs: Duration) -> NaiveDate {
+        self + -rhs
+    }
+}
+
+impl Add<Duration> for NaiveDateTime {
+    type Output = NaiveDateTime;
+
+    fn add(self, rhs: Duration) -> NaiveDateTime {
+        rhs.checked_add_to_naive_datetime(self)
+            .expect("`NaiveDateTime + Duration` overflowed")
+    }
+}
+
+impl Sub<Duration> for NaiveDateTime {
+    type Output = NaiveDateTime;
+
+    fn sub(self, rhs: Duration) -> NaiveDateTime {
+        self + -rhs
+    }
+}
+
+impl<Tz: TimeZone> Add<Duration> for DateTime<Tz> {
+    type Output = DateTime<Tz>;
+
+    fn add(self, rhs: Duration) -> DateTime<Tz> {
+        rhs.checked_add_to_datetime(self)
+            .expect("`DateTime + Duration` overflowed or ended on an invalid local time")
+    }
+}
+
+impl<Tz: TimeZone> Sub<Duration> for DateTime<Tz> {
+    type Output = DateTime<Tz>;
+
+    fn sub(self, rhs: Duration) -> DateTime<Tz> {
+        self + -rhs
+    }
+}
+
+/// Formats the duration the way neo4j does, e.g. `P1Y2M3DT4H5M6.7S`
+impl Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "P")?;
+        let (years, months) = (self.months / 12, self.months % 12);
+        for (value, unit) in [(years, 'Y'), (months, 'M'), (self.days, 'D')] {
+            if value != 0 {
+                write!(f, "{}{}", value, unit)?;
+            }
+        }
+
+        let nanos = self.total_nanos();
+        if nanos == 0 {
+            return if self.months == 0 && self.days == 0 {
+                write!(f, "T0S")
+            } else {
+                Ok(())
+            };
+        }
+
+        let sign = if nanos < 0 { "-" } else { "" };
+        let nanos = nanos.abs();
+        let hours = nanos / (3600 * NANOS_PER_SECOND);
+        let minutes = nanos / (60 * NANOS_PER_SECOND) % 60;
+        let seconds = nanos / NANOS_PER_SECOND % 60;
+        let fraction = nanos % NANOS_PER_SECOND;
+        write!(f, "T")?;
+        if hours != 0 {
+            write!(f, "{}{}H", sign, hours)?;
+        }
+        if minutes != 0 {
+            write!(f, "{}{}M", sign, minutes)?;
+        }
+        if seconds != 0 || fraction != 0 {
+            write!(f, "{}{}", sign, seconds)?;
+            if fraction != 0 {
+                let fraction = format!("{:09}", fraction);
+                write!(f, ".{}", fraction.trim_end_matches('0'))?;
+            }
+            write!(f, "S")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses an ISO-8601 duration such as `P1Y2M3W4DT5H6M7.008S`.
+///
+/// Each component may be negative, the whole duration may be negated with a leading `-`, and
+/// only the seconds may have a fraction (up to nanosecond precision).
+impl FromStr for Duration {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Duration> {
+        parse_duration(input).ok_or_else(|| {
+            Error::DeserializationError(format!("invalid ISO-8601 duration `{}`", input))
+        })
+    }
+}
+
+fn parse_duration(input: &str) -> Option<Duration> {
+    let (negate, rest) = match input.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, input),
+    };
+    let rest = rest.strip_prefix('P')?;
+    let (date, time) = match rest.find('T') {
+        Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+        None => (rest, None),
+    };
+    if date.is_empty() && time.map_or(true, str::is_empty) {
+        return None;
+    }
+
+    let (mut months, mut days, mut seconds, mut nanos) = (0i64, 0i64, 0i64, 0i64);
+    for (value, fraction, unit) in components(date, &['Y', 'M', 'W', 'D'])? {
+        if fraction != 0 {
+            return None;
+        }
+        match unit {
+            'Y' => months = months.checked_add(value.checked_mul(12)?)?,
+            'M' => months = months.checked_add(value)?,
+            'W' => days = days.checked_add(value.checked_mul(7)?)?,
+            _ => days = days.checked_add(value)?,
+        }
+    }
+    if let Some(time) = time {
+        if time.is_empty() {
+            return None;
+        }
+        for (value, fraction, unit) in components(time, &['H', 'M', 'S'])? {
+            match unit {
+                'H' if fraction == 0 => seconds = seconds.checked_add(value.checked_mul(3600)?)?,
+                'M' if fraction == 0 => seconds = seconds.checked_add(value.checked_mul(60)?)?,
+                'S' => {
+                    seconds = seconds.checked_add(value)?;
+                    nanos = fraction;
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    let duration = Duration::checked_new(months, days, seconds, nanos)?;
+    if negate {
+        duration.checked_neg()
+    } else {
+        Some(duration)
+    }
+}
+
+/// Splits `1Y-2M3.5S` into `(value, signed nanosecond fraction, unit)` triples, the units must
+/// appear in the given order.
+fn components(input: &str, units: &[char]) -> Option<Vec<(i64, i64, char)>> {
+    let mut result = Vec::new();
+    let mut allowed = units;
+    let mut rest = input;
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| c.is_ascii_alphabetic())?;
+        let (number, unit) = (&rest[..end], rest[end..].chars().next()?);
+        let position = allowed.iter().position(|&u| u == unit)?;
+        allowed = &allowed[position + 1..];
+        rest = &rest[end + 1..];
+
+        let (negative, number) = match number.strip_prefix('-') {
+            Some(number) => (true, number),
+            None => (false, number),
+        };
+        let (whole, fraction) = match number.find(['.', ',']) {
+            Some(index) => (&number[..index], Some(&number[index + 1..])),
+            None => (number, None),
+        };
+        if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let mut value: i64 = whole.parse().ok()?;
+        let mut nanos = match fraction {
+            Some(f) if !f.is_empty() && f.len() <= 9 && f.bytes().all(|b| b.is_ascii_digit()) => {
+                format!("{:0<9}", f).parse::<i64>().ok()?
+            }
+            Some(_) => return None,
+            None => 0,
+        };
+        if negative {
+            value = -value;
+            nanos = -nanos;
+        }
+        result.push((value, nanos, unit));
+    }
+    Some(result)
+}
+
+impl From<BoltDuration> for Duration {
+    /// Out of range nanoseconds are carried into the seconds, saturating on overflow
+    fn from(duration: BoltDuration) -> Self {
+        let nanoseconds = duration.nanoseconds();
+        Duration {
+            months: duration.months(),
+            days: duration.days(),
+            seconds: duration
+                .seconds()
+                .saturating_add(nanoseconds.div_euclid(NANOS_PER_SECOND as i64)),
+            nanoseconds: nanoseconds.rem_euclid(NANOS_PER_SECOND as i64) as i32,
+        }
+    }
+}
+
+impl Into<BoltDuration> for Duration {
+    fn into(self) -> BoltDuration {
+        BoltDuration::new(
+            self.months.into(),
+            self.days.into(),
+            self.seconds.into(),
+            (self.nanoseconds as i64).into(),
+        )
+    }
+}
+
+impl Into<BoltType> for Duration {
+    fn into(self) -> BoltType {
+        BoltType::Duration(self.into())
+    }
+}
+
+impl TryFrom<BoltType> for Duration {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<Duration> {
+        match input {
+            BoltType::Duration(d) => Ok(d.into()),
+            _ => Err(Error::ConverstionError),
+        }
+    }
+}
+
+impl From<chrono::Duration> for Duration {
+    fn from(duration: chrono::Duration) -> Self {
+        Duration::new(0, 0, duration.num_seconds(), duration.subsec_nanos() as i64)
+    }
+}
+
+/// Only durations without months or days can be converted, as those don't have a fixed length.
+impl TryFrom<Duration> for chrono::Duration {
+    type Error = Error;
+
+    fn try_from(duration: Duration) -> Result<chrono::Duration> {
+        if duration.months != 0 || duration.days != 0 {
+            return Err(Error::ConverstionError);
+        }
+        duration.time_delta().ok_or(Error::ConverstionError)
+    }
+}
+
+impl From<std::time::Duration> for Duration {
+    fn from(duration: std::time::Duration) -> Self {
+        let seconds = i64::try_from(duration.as_secs()).unwrap_or(i64::MAX);
+        Duration::new(0, 0, seconds, duration.subsec_nanos() as i64)
+    }
+}
+
+/// Only positive durations without months or days can be converted, as those don't have a fixed
+/// length.
+impl TryFrom<Duration> for std::time::Duration {
+    type Error = Error;
+
+    fn try_from(duration: Duration) -> Result<std::time::Duration> {
+        if duration.months != 0 || duration.days != 0 || duration.seconds < 0 {
+            return Err(Error::ConverstionError);
+        }
+        Ok(std::time::Duration::new(
+            duration.seconds as u64,
+            duration.nanoseconds as u32,
+        ))
+    }
+}
+
+impl TryFrom<BoltType> for chrono::Duration {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<chrono::Duration> {
+        Duration::try_from(input)?.try_into()
+    }
+}
+
+impl Into<BoltType> for chrono::Duration {
+    fn into(self) -> BoltType {
+        Duration::from(self).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveTime};
+
+    fn parse(input: &str) -> Duration {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn should_normalize_nanoseconds() {
+        let duration = Duration::new(0, 0, 1, -500_000_000);
+        assert_eq!(duration.seconds(), 0);
+        assert_eq!(duration.nanoseconds(), 500_000_000);
+
+        let duration = Duration::new(0, 0, 0, 2_500_000_000);
+        assert_eq!(duration.seconds(), 2);
+        assert_eq!(duration.nanoseconds(), 500_000_000);
+
+        assert!(Duration::checked_new(0, 0, i64::MAX, 1_000_000_000).is_none());
+    }
+
+    #[test]
+    fn should_parse_iso_8601_durations() {
+        assert_eq!(
+            parse("P1Y2M3DT4H5M6.7S"),
+            Duration::new(14, 3, 14_706, 700_000_000)
+        );
+        assert_eq!(parse("P2W"), Duration::new(0, 14, 0, 0));
+        assert_eq!(parse("PT0.000000001S"), Duration::new(0, 0, 0, 1));
+        assert_eq!(parse("PT1,5S"), Duration::new(0, 0, 1, 500_000_000));
+        assert_eq!(parse("P-1DT-1M-30S"), Duration::new(0, -1, -90, 0));
+        assert_eq!(parse("PT-0.5S"), Duration::new(0, 0, 0, -500_000_000));
+        assert_eq!(parse("-P1M1D"), Duration::new(-1, -1, 0, 0));
+    }
+
+    #[test]
+    fn should_reject_invalid_durations() {
+        for invalid in &[
+            "",
+            "P",
+            "PT",
+            "1D",
+            "P1",
+            "PD",
+            "P1S",
+            "PT1D",
+            "P1D1Y",
+            "P1.5D",
+            "PT1.5H",
+            "PT0.0000000001S",
+            "P1DT",
+            "PT1M1M",
+            "P99999999999999999999Y",
+        ] {
+            assert!(
+                invalid.parse::<Duration>().is_err(),
+                "{} should not parse",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn should_format_as_iso_8601() {
+        assert_eq!(Duration::default().to_string(), "PT0S");
+        assert_eq!(
+            Duration::new(14, 2, 30, 700).to_string(),
+            "P1Y2M2DT30.0000007S"
+        );
+        assert_eq!(
+            Duration::new(0, 0, 3_723, 500_000_000).to_string(),
+            "PT1H2M3.5S"
+        );
+        assert_eq!(Duration::new(0, -1, -90, 0).to_string(), "P-1DT-1M-30S");
+        assert_eq!(Duration::new(0, 0, 0, -500_000_000).to_string(), "PT-0.5S");
+        assert_eq!(Duration::new(1, 0, 0, 0).to_string(), "P1M");
+    }
+
+    #[test]
+    fn should_round_trip_through_strings() {
+        for duration in &[
+            Duration::new(14, 3, 14_706, 700_000_000),
+            Duration::new(-13, -3, -14_706, -700_000_000),
+            Duration::new(0, 0, -1, 1),
+            Duration::new(0, 0, 0, 0),
+        ] {
+            assert_eq!(&parse(&duration.to_string()), duration);
+        }
+    }
+
+    #[test]
+    fn should_add_and_subtract_durations() {
+        let a = Duration::new(1, 2, 3, 600_000_000);
+        let b = Duration::new(1, 1, 1, 600_000_000);
+
+        assert_eq!(a + b, Duration::new(2, 3, 5, 200_000_000));
+        assert_eq!(a - b, Duration::new(0, 1, 2, 0));
+        assert_eq!(-a, Duration::new(-1, -2, -4, 400_000_000));
+        assert!(Duration::new(i64::MAX, 0, 0, 0).checked_add(a).is_none());
+    }
+
+    #[test]
+    fn should_add_to_dates_with_cypher_semantics() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(date(2021, 1, 31) + parse("P1M"), date(2021, 2, 28));
+        assert_eq!(date(2021, 1, 31) + parse("P1M1D"), date(2021, 3, 1));
+        assert_eq!(date(2021, 3, 1) - parse("P1D"), date(2021, 2, 28));
+        assert_eq!(date(2021, 1, 1) + parse("PT47H"), date(2021, 1, 2));
+        assert_eq!(date(2021, 1, 1) - parse("PT47H"), date(2020, 12, 31));
+
+        let datetime = date(2021, 1, 31).and_time(NaiveTime::from_hms_opt(23, 0, 0).unwrap());
+        assert_eq!(
+            datetime + parse("P1MT2H"),
+            date(2021, 3, 1).and_time(NaiveTime::from_hms_opt(1, 0, 0).unwrap())
+        );
+
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let zoned = offset.from_local_datetime(&datetime).unwrap();
+        assert_eq!(
+            (zoned + parse("P1D")).to_rfc3339(),
+            "2021-02-01T23:00:00+01:00"
+        );
+        assert!(parse("P1000000000000D")
+            .checked_add_to_date(date(2021, 1, 1))
+            .is_none());
+    }
+
+    #[test]
+    fn should_convert_only_when_lossless() {
+        let exact = Duration::new(0, 0, 90, 5);
+        assert_eq!(
+            chrono::Duration::try_from(exact).unwrap(),
+            chrono::Duration::seconds(90) + chrono::Duration::nanoseconds(5)
+        );
+        assert_eq!(
+            std::time::Duration::try_from(exact).unwrap(),
+            std::time::Duration::new(90, 5)
+        );
+        assert_eq!(
+            Duration::from(chrono::Duration::milliseconds(-1500)),
+            Duration::new(0, 0, -1, -500_000_000)
+        );
+        assert_eq!(Duration::from(std::time::Duration::new(90, 5)), exact);
+
+        assert!(chrono::Duration::try_from(Duration::new(1, 0, 0, 0)).is_err());
+        assert!(chrono::Duration::try_from(Duration::new(0, 1, 0, 0)).is_err());
+        assert!(std::time::Duration::try_from(Duration::new(0, 1, 0, 0)).is_err());
+        assert!(std::time::Duration::try_from(Duration::new(0, 0, -1, 0)).is_err());
+    }
+
+    #[test]
+    fn should_convert_to_and_from_bolt_types() {
+        let duration = Duration::new(14, -2, 30, 700);
+
+        let bolt: BoltType = duration.into();
+
+        assert_eq!(
+            bolt,
+            BoltType::Duration(BoltDuration::new(
+                14.into(),
+                (-2).into(),
+                30.into(),
+                700.into()
+            ))
+        );
+        assert_eq!(Duration::try_from(bolt).unwrap(), duration);
+    }
+}
@@ -0,0 +1,470 @@
+use crate::errors::*;
+use crate::row::{Node, Relation};
+use crate::types::{write_identifier, BoltMap, BoltType};
+use crate::Query;
+use std::convert::TryFrom;
+
+/// A struct stored as nodes, implemented by `#[derive(Label)]`
+pub trait NodeEntity: TryFrom<Node, Error = Error> {
+    /// Labels of the nodes
+    const LABELS: &'static [&'static str];
+
+    /// The property set on the nodes marked as deleted, which are left out of the related nodes
+    const SOFT_DELETE: Option<&'static str> = None;
+
+    /// The properties identifying this node, e.g. `{id: 42}`
+    fn key(&self) -> BoltMap;
+
+    /// The nodes related to this one, returned as `n`
+    fn related_query<T: NodeEntity>(&self, relation: &str, direction: Direction) -> Query {
+        let mut query = Pattern::new();
+        query.push("match ");
+        query.node("a", Self::LABELS, Some(self.key()));
+        query.edge(relation, direction);
+        query.node("n", T::LABELS, None);
+        query.not_deleted("n", T::SOFT_DELETE);
+        query.push(" return n");
+        query.into_query()
+    }
+
+    /// Makes the targets the only nodes related to this one: deletes the relationships to other
+    /// nodes, then merges those to the targets. The relationships to soft-deleted nodes are kept,
+    /// as they are left out of the loaded relations
+    fn sync_related_queries<'a, T, I>(
+        &self,
+        relation: &str,
+        direction: Direction,
+        targets: I,
+    ) -> Vec<Query>
+    where
+        T: NodeEntity + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        let keys = targets.into_iter().map(T::key).collect::<Vec<_>>();
+        let mut delete = Pattern::new();
+        delete.push("match ");
+        delete.node("a", Self::LABELS, Some(self.key()));
+        delete.edge(relation, direction);
+        delete.node("m", T::LABELS, None);
+        delete.not_deleted("m", T::SOFT_DELETE);
+        let properties = match keys.first() {
+            Some(key) => key.keys().map(|k| k.value.clone()).collect::<Vec<_>>(),
+            None => {
+                delete.push(" delete r");
+                return vec![delete.into_query()];
+            }
+        };
+
+        let projection = properties
+            .iter()
+            .map(|property| {
+                let mut text = ".".to_owned();
+                write_identifier(&mut text, property);
+                text
+            })
+            .collect::<Vec<_>>();
+        delete.push(&format!(
+            " {} not m{{{}}} in $keys delete r",
+            if T::SOFT_DELETE.is_some() {
+                "and"
+            } else {
+                "where"
+            },
+            projection.join(", ")
+        ));
+
+        let mut merge = Pattern::new();
+        merge.push("match ");
+        merge.node("a", Self::LABELS, Some(self.key()));
+        merge.push(" unwind $keys as key match (m");
+        for label in T::LABELS {
+            merge.push(":");
+            write_identifier(&mut merge.text, label);
+        }
+        let matching = properties
+            .iter()
+            .map(|property| {
+                let mut name = String::new();
+                write_identifier(&mut name, property);
+                format!("{}: key.{}", name, name)
+            })
+            .collect::<Vec<_>>();
+        merge.push(&format!(" {{{}}}) merge (a)", matching.join(", ")));
+        merge.edge(relation, direction);
+        merge.push("(m)");
+
+        vec![
+            delete.into_query().param("keys", keys.clone()),
+            merge.into_query().param("keys", keys),
+        ]
+    }
+}
+
+/// A struct whose fields are stored as properties of the nodes or relationships embedding it with
+/// `#[neo4j(flatten)]`, implemented by `#[derive(Properties)]`
+pub trait Properties: Sized {
+    /// The properties of the fields
+    fn properties(&self) -> BoltMap;
+
+    /// Reads the fields from the properties returned by `get`
+    fn from_properties(get: &dyn Fn(&str) -> Option<BoltType>) -> Result<Self>;
+}
+
+/// Direction of the relationships followed from a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// `(n)-[r]->(m)`
+    Outgoing,
+    /// `(n)<-[r]-(m)`
+    Incoming,
+}
+
+/// A struct stored as relationships between two kinds of nodes, implemented by
+/// `#[derive(Relationship)]`.
+///
+/// The queries match both nodes on their keys, relationships are then told apart by their type
+/// and direction only.
+pub trait RelationshipEntity: TryFrom<Relation, Error = Error> {
+    /// The start node of the relationships
+    type From: NodeEntity;
+    /// The end node of the relationships
+    type To: NodeEntity;
+
+    /// Type of the relationships
+    const TYPE: &'static str;
+
+    /// The properties of this relationship
+    fn properties(&self) -> BoltMap;
+
+    /// Creates this relationship between the nodes, returned as `r`
+    fn connect_query(&self, from: &Self::From, to: &Self::To) -> Query {
+        let mut query = Pattern::new();
+        query.push("match ");
+        query.node("a", Self::From::LABELS, Some(from.key()));
+        query.push(", ");
+        query.node("b", Self::To::LABELS, Some(to.key()));
+        query.push(" create (a)");
+        query.relationship::<Self>();
+        query.push("(b) set r = $props return r");
+        query.into_query().param("props", self.properties())
+    }
+
+    /// Deletes the relationships from the start to the end node
+    fn disconnect_query(from: &Self::From, to: &Self::To) -> Query {
+        let mut query = Pattern::between::<Self>(from, to);
+        query.push(" delete r");
+        query.into_query()
+    }
+
+    /// Overwrites the properties of the relationships from the start to the end node
+    fn update_query(&self, from: &Self::From, to: &Self::To) -> Query {
+        let mut query = Pattern::between::<Self>(from, to);
+        query.push(" set r = $props");
+        query.into_query().param("props", self.properties())
+    }
+
+    /// The relationships leaving the node as `r`, along with their end nodes as `n`
+    fn outgoing_query(from: &Self::From) -> Query {
+        let mut query = Pattern::new();
+        query.push("match ");
+        query.node("a", Self::From::LABELS, Some(from.key()));
+        query.relationship::<Self>();
+        query.node("n", Self::To::LABELS, None);
+        query.not_deleted("n", Self::To::SOFT_DELETE);
+        query.push(" return r, n");
+        query.into_query()
+    }
+
+    /// The relationships reaching the node as `r`, along with their start nodes as `n`
+    fn incoming_query(to: &Self::To) -> Query {
+        let mut query = Pattern::new();
+        query.push("match ");
+        query.node("n", Self::From::LABELS, None);
+        query.relationship::<Self>();
+        query.node("b", Self::To::LABELS, Some(to.key()));
+        query.not_deleted("n", Self::From::SOFT_DELETE);
+        query.push(" return r, n");
+        query.into_query()
+    }
+}
+
+/// Builds a query matching nodes on their keys, sent as parameters named after the node
+/// variable, e.g. `(a:Person {id: $a0})`
+struct Pattern {
+    text: String,
+    params: BoltMap,
+}
+
+impl Pattern {
+    fn new() -> Self {
+        Pattern {
+            text: String::new(),
+            params: BoltMap::default(),
+        }
+    }
+
+    /// `match (a:From {...})-[r:TYPE]->(b:To {...})`
+    fn between<R: RelationshipEntity>(from: &R::From, to: &R::To) -> Self {
+        let mut query = Pattern::new();
+        query.push("match ");
+        query.node("a", R::From::LABELS, Some(from.key()));
+        query.relationship::<R>();
+        query.node("b", R::To::LABELS, Some(to.key()));
+        query
+    }
+
+    fn push(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+
+    fn node(&mut self, variable: &str, labels: &[&str], key: Option<BoltMap>) {
+        self.text.push('(');
+        self.text.push_str(variable);
+        for label in labels {
+            self.text.push(':');
+            write_identifier(&mut self.text, label);
+        }
+        if let Some(key) = key {
+            let properties = key
+                .into_iter()
+                .enumerate()
+                .map(|(index, (property, value))| {
+                    let param = format!("{}{}", variable, index);
+                    let mut text = String::new();
+                    write_identifier(&mut text, &property.value);
+                    text.push_str(": $");
+                    text.push_str(&param);
+                    self.params.put(param.into(), value);
+                    text
+                })
+                .collect::<Vec<_>>();
+            self.text.push_str(" {");
+            self.text.push_str(&properties.join(", "));
+            self.text.push('}');
+        }
+        self.text.push(')');
+    }
+
+    /// `where n.deleted_at is null`, when the nodes can be marked as deleted
+    fn not_deleted(&mut self, variable: &str, property: Option<&str>) {
+        if let Some(property) = property {
+            self.text.push_str(" where ");
+            self.text.push_str(variable);
+            self.text.push('.');
+            write_identifier(&mut self.text, property);
+            self.text.push_str(" is null");
+        }
+    }
+
+    fn relationship<R: RelationshipEntity>(&mut self) {
+        self.edge(R::TYPE, Direction::Outgoing);
+    }
+
+    fn edge(&mut self, relation: &str, direction: Direction) {
+        self.text.push_str(match direction {
+            Direction::Outgoing => "-[r:",
+            Direction::Incoming => "<-[r:",
+        });
+        write_identifier(&mut self.text, relation);
+        self.text.push_str(match direction {
+            Direction::Outgoing => "]->",
+            Direction::Incoming => "]-",
+        });
+    }
+
+    fn into_query(self) -> Query {
+        Query::new_with_params(&self.text, self.params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BoltString;
+
+    struct Person {
+        id: i64,
+    }
+
+    impl TryFrom<Node> for Person {
+        type Error = Error;
+
+        fn try_from(node: Node) -> Result<Self> {
+            Ok(Person {
+                id: node.get("id").ok_or(Error::NoMatch)?,
+            })
+        }
+    }
+
+    impl NodeEntity for Person {
+        const LABELS: &'static [&'static str] = &["Person"];
+
+        fn key(&self) -> BoltMap {
+            vec![(BoltString::from("id"), self.id.into())]
+                .into_iter()
+                .collect()
+        }
+    }
+
+    struct Team {
+        org: String,
+        name: String,
+    }
+
+    impl TryFrom<Node> for Team {
+        type Error = Error;
+
+        fn try_from(_: Node) -> Result<Self> {
+            Err(Error::NoMatch)
+        }
+    }
+
+    impl NodeEntity for Team {
+        const LABELS: &'static [&'static str] = &["Team", "Org Unit"];
+        const SOFT_DELETE: Option<&'static str> = Some("deleted at");
+
+        fn key(&self) -> BoltMap {
+            vec![
+                (BoltString::from("org"), self.org.clone().into()),
+                (BoltString::from("team name"), self.name.clone().into()),
+            ]
+            .into_iter()
+            .collect()
+        }
+    }
+
+    struct MemberOf {
+        since: i64,
+    }
+
+    impl TryFrom<Relation> for MemberOf {
+        type Error = Error;
+
+        fn try_from(relation: Relation) -> Result<Self> {
+            Ok(MemberOf {
+                since: relation.get("since").ok_or(Error::NoMatch)?,
+            })
+        }
+    }
+
+    impl RelationshipEntity for MemberOf {
+        type From = Person;
+        type To = Team;
+
+        const TYPE: &'static str = "MEMBER_OF";
+
+        fn properties(&self) -> BoltMap {
+            vec![(BoltString::from("since"), self.since.into())]
+                .into_iter()
+                .collect()
+        }
+    }
+
+    fn nodes() -> (Person, Team) {
+        let team = Team {
+            org: "acme".to_owned(),
+            name: "core".to_owned(),
+        };
+        (Person { id: 42 }, team)
+    }
+
+    #[test]
+    fn should_find_related_nodes() {
+        let (person, _) = nodes();
+        assert_eq!(
+            person.related_query::<Team>("MEMBER_OF", Direction::Outgoing),
+            Query::new("match (a:Person {id: $a0})-[r:MEMBER_OF]->(n:Team:`Org Unit`) where n.`deleted at` is null return n")
+                .param("a0", 42)
+        );
+        assert_eq!(
+            person.related_query::<Person>("MANAGES", Direction::Incoming),
+            Query::new("match (a:Person {id: $a0})<-[r:MANAGES]-(n:Person) return n")
+                .param("a0", 42)
+        );
+    }
+
+    #[test]
+    fn should_sync_related_nodes() {
+        let (person, team) = nodes();
+        let queries = person.sync_related_queries("MEMBER_OF", Direction::Outgoing, vec![&team]);
+        assert_eq!(
+            queries,
+            vec![
+                Query::new("match (a:Person {id: $a0})-[r:MEMBER_OF]->(m:Team:`Org Unit`) where m.`deleted at` is null and not m{.org, .`team name`} in $keys delete r")
+                    .param("a0", 42)
+                    .param("keys", vec![team.key()]),
+                Query::new("match (a:Person {id: $a0}) unwind $keys as key match (m:Team:`Org Unit` {org: key.org, `team name`: key.`team name`}) merge (a)-[r:MEMBER_OF]->(m)")
+                    .param("a0", 42)
+                    .param("keys", vec![team.key()]),
+            ]
+        );
+
+        let queries =
+            person.sync_related_queries::<Team, _>("MEMBER_OF", Direction::Outgoing, None);
+        assert_eq!(
+            queries,
+            vec![Query::new(
+                "match (a:Person {id: $a0})-[r:MEMBER_OF]->(m:Team:`Org Unit`) where m.`deleted at` is null delete r"
+            )
+            .param("a0", 42)]
+        );
+
+        let queries = team.sync_related_queries("MEMBER_OF", Direction::Incoming, vec![&person]);
+        assert_eq!(
+            queries[0],
+            Query::new("match (a:Team:`Org Unit` {org: $a0, `team name`: $a1})<-[r:MEMBER_OF]-(m:Person) where not m{.id} in $keys delete r")
+                .param("a0", "acme")
+                .param("a1", "core")
+                .param("keys", vec![person.key()])
+        );
+    }
+
+    #[test]
+    fn should_connect_nodes_matched_on_their_keys() {
+        let (person, team) = nodes();
+        assert_eq!(
+            MemberOf { since: 2020 }.connect_query(&person, &team),
+            Query::new("match (a:Person {id: $a0}), (b:Team:`Org Unit` {org: $b0, `team name`: $b1}) create (a)-[r:MEMBER_OF]->(b) set r = $props return r")
+                .param("a0", 42)
+                .param("b0", "acme")
+                .param("b1", "core")
+                .param("props", MemberOf { since: 2020 }.properties())
+        );
+    }
+
+    #[test]
+    fn should_match_relationships_between_nodes() {
+        let (person, team) = nodes();
+        assert_eq!(
+            MemberOf::disconnect_query(&person, &team),
+            Query::new("match (a:Person {id: $a0})-[r:MEMBER_OF]->(b:Team:`Org Unit` {org: $b0, `team name`: $b1}) delete r")
+                .param("a0", 42)
+                .param("b0", "acme")
+                .param("b1", "core")
+        );
+        assert_eq!(
+            MemberOf { since: 2021 }.update_query(&person, &team),
+            Query::new("match (a:Person {id: $a0})-[r:MEMBER_OF]->(b:Team:`Org Unit` {org: $b0, `team name`: $b1}) set r = $props")
+                .param("a0", 42)
+                .param("b0", "acme")
+                .param("b1", "core")
+                .param("props", MemberOf { since: 2021 }.properties())
+        );
+    }
+
+    #[test]
+    fn should_follow_relationships_from_either_end() {
+        let (person, team) = nodes();
+        assert_eq!(
+            MemberOf::outgoing_query(&person),
+            Query::new("match (a:Person {id: $a0})-[r:MEMBER_OF]->(n:Team:`Org Unit`) where n.`deleted at` is null return r, n")
+                .param("a0", 42)
+        );
+        assert_eq!(
+            MemberOf::incoming_query(&team),
+            Query::new("match (n:Person)-[r:MEMBER_OF]->(b:Team:`Org Unit` {org: $b0, `team name`: $b1}) return r, n")
+                .param("b0", "acme")
+                .param("b1", "core")
+        );
+    }
+}
@@ -2,7 +2,9 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug)]
 pub enum Error {
-    IOError { detail: String },
+    IOError {
+        detail: String,
+    },
     ConnectionError,
     StringTooLong,
     MapTooBig,
@@ -18,6 +20,33 @@ pub enum Error {
     InvalidTypeMarker(String),
     DeserializationError(String),
     NoMatch,
+    /// A property of a struct deriving `Label` is missing from the node or row
+    MissingProperty {
+        target: String,
+        field: String,
+        property: String,
+    },
+    /// A property of a struct deriving `Label` can't be converted to the type of the field
+    InvalidProperty {
+        target: String,
+        field: String,
+        property: String,
+        expected: String,
+    },
+    /// The value wrapped by a newtype deriving `Value` can't be converted, `source` is the error
+    /// of the wrapped type
+    InvalidValue {
+        target: String,
+        source: Box<Error>,
+    },
+    /// The node updated from a struct deriving `Label` no longer has the `#[neo4j(version)]` of
+    /// the struct, it was written since the struct was read
+    VersionConflict {
+        target: String,
+    },
+    /// A label, relationship type or property key which can't be written in a query, it is
+    /// empty or contains the null character
+    InvalidIdentifier(String),
 }
 
 impl std::convert::From<std::io::Error> for Error {
@@ -28,6 +57,12 @@ impl std::convert::From<std::io::Error> for Error {
     }
 }
 
+impl std::convert::From<std::convert::Infallible> for Error {
+    fn from(e: std::convert::Infallible) -> Self {
+        match e {}
+    }
+}
+
 impl std::convert::From<deadpool::managed::PoolError<Error>> for Error {
     fn from(e: deadpool::managed::PoolError<Error>) -> Self {
         match e {
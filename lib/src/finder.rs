@@ -0,0 +1,434 @@
+use crate::entity::Direction;
+use crate::errors::*;
+use crate::row::Node;
+use crate::types::{write_identifier, BoltMap, BoltType};
+use crate::{Execute, Query};
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+
+/// Comparison between a property and a value in a [`Finder`] condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// The value is a list containing the property
+    In,
+    StartsWith,
+    EndsWith,
+    Contains,
+}
+
+impl Operator {
+    fn as_cypher(&self) -> &'static str {
+        match self {
+            Operator::Eq => "=",
+            Operator::Ne => "<>",
+            Operator::Gt => ">",
+            Operator::Gte => ">=",
+            Operator::Lt => "<",
+            Operator::Lte => "<=",
+            Operator::In => "in",
+            Operator::StartsWith => "starts with",
+            Operator::EndsWith => "ends with",
+            Operator::Contains => "contains",
+        }
+    }
+}
+
+/// Sort direction of a [`Finder`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+/// Finds nodes with the given labels, filtered, sorted and paginated, converting them to `T`.
+///
+/// Values are always sent as query parameters. It is mostly used through the typed finders
+/// generated by `#[derive(Label)]`, e.g. `Person::find().where_age_gt(30).limit(10)`.
+///
+/// ```
+/// use neo4jrs::{Finder, Node, Operator, Order};
+///
+/// let finder = Finder::<Node>::new(&["Person"])
+///     .filter("age", Operator::Gt, 30)
+///     .order_by("name", Order::Asc)
+///     .limit(10);
+/// assert_eq!(
+///     finder.to_string(),
+///     "match (n:Person) where n.age > $p0 return n order by n.name asc limit $limit"
+/// );
+/// ```
+pub struct Finder<T> {
+    labels: String,
+    conditions: Vec<String>,
+    params: BoltMap,
+    order: Vec<String>,
+    skip: Option<usize>,
+    limit: Option<usize>,
+    includes: Vec<Include<T>>,
+    target: PhantomData<fn() -> T>,
+}
+
+/// Related nodes returned as a list along with each node
+struct Include<T> {
+    /// `[(n)-[:TYPE]->(m:Label) | m] as column`
+    expression: String,
+    column: String,
+    fill: fn(&mut T, Vec<Node>) -> Result<()>,
+}
+
+impl<T> Clone for Include<T> {
+    fn clone(&self) -> Self {
+        Include {
+            expression: self.expression.clone(),
+            column: self.column.clone(),
+            fill: self.fill,
+        }
+    }
+}
+
+impl<T> Finder<T> {
+    pub fn new(labels: &[&str]) -> Self {
+        let mut pattern = String::new();
+        for label in labels {
+            pattern.push(':');
+            write_identifier(&mut pattern, label);
+        }
+        Finder {
+            labels: pattern,
+            conditions: Vec::new(),
+            params: BoltMap::default(),
+            order: Vec::new(),
+            skip: None,
+            limit: None,
+            includes: Vec::new(),
+            target: PhantomData,
+        }
+    }
+
+    /// Keeps the nodes whose property compares to the value, conditions are combined with `and`
+    pub fn filter<V: Into<BoltType>>(
+        mut self,
+        property: &str,
+        operator: Operator,
+        value: V,
+    ) -> Self {
+        let param = format!("p{}", self.params.len());
+        let mut condition = "n.".to_owned();
+        write_identifier(&mut condition, property);
+        condition.push_str(&format!(" {} ${}", operator.as_cypher(), param));
+        self.conditions.push(condition);
+        self.params.put(param.into(), value.into());
+        self
+    }
+
+    /// Keeps the nodes where the property is null, or set when `is_null` is false
+    pub fn filter_null(mut self, property: &str, is_null: bool) -> Self {
+        let mut condition = "n.".to_owned();
+        write_identifier(&mut condition, property);
+        condition.push_str(if is_null { " is null" } else { " is not null" });
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Sorts by the property, after the previous orders
+    pub fn order_by(mut self, property: &str, order: Order) -> Self {
+        let mut sort = "n.".to_owned();
+        write_identifier(&mut sort, property);
+        sort.push_str(match order {
+            Order::Asc => " asc",
+            Order::Desc => " desc",
+        });
+        self.order.push(sort);
+        self
+    }
+
+    pub fn skip(mut self, skip: usize) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Also returns the nodes related to each node through the relationship, as a list in the
+    /// column, which `all` and `first` pass to `fill` with the converted node. Related nodes where
+    /// the `deleted` property is set are left out.
+    pub fn include(
+        mut self,
+        column: &str,
+        relation: &str,
+        direction: Direction,
+        labels: &[&str],
+        deleted: Option<&str>,
+        fill: fn(&mut T, Vec<Node>) -> Result<()>,
+    ) -> Self {
+        let mut expression = String::from("[(n)");
+        expression.push_str(match direction {
+            Direction::Outgoing => "-[:",
+            Direction::Incoming => "<-[:",
+        });
+        write_identifier(&mut expression, relation);
+        expression.push_str(match direction {
+            Direction::Outgoing => "]->(m",
+            Direction::Incoming => "]-(m",
+        });
+        for label in labels {
+            expression.push(':');
+            write_identifier(&mut expression, label);
+        }
+        expression.push(')');
+        if let Some(property) = deleted {
+            expression.push_str(" where m.");
+            write_identifier(&mut expression, property);
+            expression.push_str(" is null");
+        }
+        expression.push_str(" | m] as ");
+        write_identifier(&mut expression, column);
+        self.includes.push(Include {
+            expression,
+            column: column.to_owned(),
+            fill,
+        });
+        self
+    }
+
+    /// The query returning the matching nodes as `n`
+    pub fn query(&self) -> Query {
+        self.with_pagination(Query::new_with_params(
+            &self.to_string(),
+            self.params.clone(),
+        ))
+    }
+
+    fn with_pagination(&self, mut query: Query) -> Query {
+        if let Some(skip) = self.skip {
+            query = query.param("skip", skip as i64);
+        }
+        if let Some(limit) = self.limit {
+            query = query.param("limit", limit as i64);
+        }
+        query
+    }
+
+    fn write_match(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "match (n{})", self.labels)?;
+        if !self.conditions.is_empty() {
+            write!(f, " where {}", self.conditions.join(" and "))?;
+        }
+        Ok(())
+    }
+
+    fn write_pagination(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.order.is_empty() {
+            write!(f, " order by {}", self.order.join(", "))?;
+        }
+        if self.skip.is_some() {
+            f.write_str(" skip $skip")?;
+        }
+        if self.limit.is_some() {
+            f.write_str(" limit $limit")?;
+        }
+        Ok(())
+    }
+
+    /// Number of matching nodes, within the skip and limit if any
+    pub async fn count(self, graph: &impl Execute) -> Result<usize> {
+        let query = self.with_pagination(Query::new_with_params(
+            &Count(&self).to_string(),
+            self.params.clone(),
+        ));
+        let mut result = graph.execute(query).await?;
+        match result.next().await? {
+            Some(row) => Ok(row.get::<i64>("count").unwrap_or(0) as usize),
+            None => Ok(0),
+        }
+    }
+
+    pub async fn exists(self, graph: &impl Execute) -> Result<bool> {
+        Ok(self.limit(1).count(graph).await? > 0)
+    }
+}
+
+impl<T: TryFrom<Node, Error = Error>> Finder<T> {
+    pub async fn all(self, graph: &impl Execute) -> Result<Vec<T>> {
+        let mut result = graph.execute(self.query()).await?;
+        let mut all = Vec::new();
+        while let Some(row) = result.next().await? {
+            if let Some(node) = row.get::<Node>("n") {
+                let mut item = T::try_from(node)?;
+                for include in &self.includes {
+                    let related = row.get::<Vec<Node>>(&include.column).unwrap_or_default();
+                    (include.fill)(&mut item, related)?;
+                }
+                all.push(item);
+            }
+        }
+        Ok(all)
+    }
+
+    pub async fn first(self, graph: &impl Execute) -> Result<Option<T>> {
+        Ok(self.limit(1).all(graph).await?.into_iter().next())
+    }
+}
+
+/// Renders the query, values appear as parameters
+impl<T> fmt::Display for Finder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_match(f)?;
+        f.write_str(" return n")?;
+        for include in &self.includes {
+            write!(f, ", {}", include.expression)?;
+        }
+        self.write_pagination(f)
+    }
+}
+
+struct Count<'a, T>(&'a Finder<T>);
+
+impl<'a, T> fmt::Display for Count<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_match(f)?;
+        if self.0.skip.is_some() || self.0.limit.is_some() {
+            f.write_str(" with n")?;
+            self.0.write_pagination(f)?;
+        }
+        f.write_str(" return count(n) as count")
+    }
+}
+
+impl<T> Clone for Finder<T> {
+    fn clone(&self) -> Self {
+        Finder {
+            labels: self.labels.clone(),
+            conditions: self.conditions.clone(),
+            params: self.params.clone(),
+            order: self.order.clone(),
+            skip: self.skip,
+            limit: self.limit,
+            includes: self.includes.clone(),
+            target: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for Finder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Finder")
+            .field("query", &self.to_string())
+            .field("params", &self.params)
+            .field("skip", &self.skip)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finder() -> Finder<Node> {
+        Finder::new(&["Person", "Team Member"])
+    }
+
+    #[test]
+    fn should_match_all_nodes_with_the_labels() {
+        assert_eq!(
+            finder().to_string(),
+            "match (n:Person:`Team Member`) return n"
+        );
+    }
+
+    #[test]
+    fn should_send_values_as_parameters() {
+        let finder = finder()
+            .filter("age", Operator::Gte, 30)
+            .filter("first name", Operator::StartsWith, "A') detach delete n //")
+            .filter_null("email", false);
+
+        assert_eq!(
+            finder.to_string(),
+            "match (n:Person:`Team Member`) where n.age >= $p0 and n.`first name` starts with $p1 and n.email is not null return n"
+        );
+        let query = finder.query();
+        assert_eq!(
+            query,
+            Query::new(&finder.to_string())
+                .param("p0", 30)
+                .param("p1", "A') detach delete n //")
+        );
+    }
+
+    #[test]
+    fn should_sort_and_paginate() {
+        let finder = finder()
+            .filter("name", Operator::In, vec!["a", "b"])
+            .order_by("name", Order::Asc)
+            .order_by("age", Order::Desc)
+            .skip(20)
+            .limit(10);
+
+        assert_eq!(
+            finder.to_string(),
+            "match (n:Person:`Team Member`) where n.name in $p0 return n order by n.name asc, n.age desc skip $skip limit $limit"
+        );
+        assert_eq!(
+            Count(&finder).to_string(),
+            "match (n:Person:`Team Member`) where n.name in $p0 with n order by n.name asc, n.age desc skip $skip limit $limit return count(n) as count"
+        );
+        assert_eq!(
+            finder.query(),
+            Query::new(&finder.to_string())
+                .param("p0", vec!["a", "b"])
+                .param("skip", 20)
+                .param("limit", 10)
+        );
+    }
+
+    #[test]
+    fn should_include_related_nodes() {
+        let finder = finder()
+            .include(
+                "team",
+                "MEMBER_OF",
+                Direction::Outgoing,
+                &["Team"],
+                None,
+                |_, _| Ok(()),
+            )
+            .include(
+                "reports",
+                "REPORTS TO",
+                Direction::Incoming,
+                &[],
+                Some("deleted at"),
+                |_, _| Ok(()),
+            )
+            .limit(1);
+
+        assert_eq!(
+            finder.to_string(),
+            "match (n:Person:`Team Member`) return n, [(n)-[:MEMBER_OF]->(m:Team) | m] as team, [(n)<-[:`REPORTS TO`]-(m) where m.`deleted at` is null | m] as reports limit $limit"
+        );
+        assert_eq!(
+            Count(&finder).to_string(),
+            "match (n:Person:`Team Member`) with n limit $limit return count(n) as count"
+        );
+    }
+
+    #[test]
+    fn should_count_without_pagination() {
+        assert_eq!(
+            Count(&finder().filter("age", Operator::Lt, 3)).to_string(),
+            "match (n:Person:`Team Member`) where n.age < $p0 return count(n) as count"
+        );
+    }
+}
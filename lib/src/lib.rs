@@ -403,6 +403,18 @@
 //!    assert_eq!(point.z(), 8.0);
 //!    assert!(result.next().await.unwrap().is_none());
 //!
+//!    //send points as parameters, distances match `point.distance` in cypher
+//!    let p1 = Point2D::cartesian(2.3, 4.5);
+//!    let p2 = Point2D::cartesian(1.1, 5.4);
+//!    let mut result = graph
+//!        .execute(query("RETURN point.distance($p1, $p2) AS dist").param("p1", p1.clone()).param("p2", p2.clone()))
+//!        .await
+//!        .unwrap();
+//!    let row = result.next().await.unwrap().unwrap();
+//!    let dist: f64 = row.get("dist").unwrap();
+//!    assert_eq!(Some(dist), p1.distance(&p2));
+//!    assert_eq!(p1.crs(), Some(Crs::Cartesian));
+//!
 //! }
 //! ```
 //!
@@ -433,6 +445,11 @@
 //!
 //! ## Durations
 //!
+//! [`Duration`] keeps the months, days, seconds and nanoseconds of a cypher duration apart, so
+//! nothing is lost in a round trip. `std::time::Duration` and `chrono::Duration` can be used as
+//! parameters too. Reading a duration with months or days into a `chrono::Duration` fails with a
+//! conversion error since those don't have a fixed length, while `std::time::Duration` counts a
+//! day as 86400 seconds and a month as 2629800 seconds.
 //!
 //! ```
 //! use neo4jrs::*;
@@ -455,6 +472,15 @@
 //!    assert_eq!(d.as_secs(), 5259600);
 //!    assert_eq!(d.subsec_nanos(), 7);
 //!    assert!(result.next().await.unwrap().is_none());
+//!
+//!    let duration: Duration = "P1Y2M3DT4.5S".parse().unwrap();
+//!    let mut result = graph
+//!        .execute(query("RETURN $d as output").param("d", duration))
+//!        .await
+//!        .unwrap();
+//!    let row = result.next().await.unwrap().unwrap();
+//!    let d: Duration = row.get("output").unwrap();
+//!    assert_eq!(d, duration);
 //! }
 //! ```
 //! ## Date
@@ -706,6 +732,25 @@
 //! }
 //! ```
 //!
+//! ### Named time zones
+//!
+//! With the `tz` feature enabled, datetimes with a zone id can be sent and read as
+//! `chrono::DateTime<chrono_tz::Tz>`, regardless of whether the server counts their seconds in
+//! local time (bolt 4) or in UTC (bolt 5). Local times that are ambiguous or skipped by a daylight
+//! saving transition resolve the way the server does: to the earlier offset, or shifted forward
+//! by the length of the gap.
+//!
+//! ```ignore
+//! let datetime = chrono_tz::Europe::Paris.with_ymd_and_hms(2021, 3, 28, 10, 0, 0).unwrap();
+//! let mut result = graph
+//!     .execute(query("RETURN $d as output").param("d", datetime))
+//!     .await
+//!     .unwrap();
+//! let row = result.next().await.unwrap().unwrap();
+//! let d: chrono::DateTime<chrono_tz::Tz> = row.get("output").unwrap();
+//! assert_eq!(d, datetime);
+//! ```
+//!
 //!
 //!
 //! ## Path
@@ -738,17 +783,22 @@
 //!    assert!(result.next().await.unwrap().is_none());
 //! }
 //! ```
+pub mod builder;
 mod config;
 mod connection;
 mod convert;
+mod duration;
+mod entity;
 mod errors;
 mod execute;
+mod finder;
 mod graph;
 mod messages;
 mod pool;
 pub mod prelude;
 mod query;
 mod row;
+mod spatial;
 mod stream;
 mod txn;
 pub mod types;
@@ -760,12 +810,19 @@ pub use crate::config::config;
 pub use crate::graph::query;
 
 pub use crate::config::{Config, ConfigBuilder};
+#[cfg(feature = "uuid")]
+pub use crate::convert::UuidBytes;
+pub use crate::duration::Duration;
+pub use crate::entity::{Direction, NodeEntity, Properties, RelationshipEntity};
 pub use crate::errors::*;
+pub use crate::finder::{Finder, Operator, Order};
 pub use crate::graph::Graph;
 pub use crate::query::Query;
 pub use crate::row::{Node, Path, Point2D, Point3D, Relation, Row, UnboundedRelation};
+pub use crate::spatial::Crs;
 pub use crate::stream::RowStream;
 pub use crate::txn::Txn;
+pub use crate::types::escape_identifier;
 pub use crate::version::Version;
 pub use execute::Execute;
-pub use neo4jrs_macros::Label;
+pub use neo4jrs_macros::{cypher, Label, Properties, Relationship, Value};
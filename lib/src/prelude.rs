@@ -1,6 +1,12 @@
 pub use crate::config::{Config as NeoConfig, ConfigBuilder as NeoConfigBuilder};
 
+pub use crate::duration::Duration as NeoDuration;
+pub use crate::entity::{
+    Direction as NeoDirection, NodeEntity as NeoNodeEntity, Properties as NeoProperties,
+    RelationshipEntity as NeoRelationshipEntity,
+};
 pub use crate::errors::{Error as NeoError, Result as NeoResult};
+pub use crate::finder::{Finder as NeoFinder, Operator as NeoOperator, Order as NeoOrder};
 pub use crate::graph::Graph as NeoGraph;
 pub use crate::query::Query as NeoQuery;
 pub use crate::row::{
@@ -8,8 +14,12 @@ pub use crate::row::{
     Relation as NeoRelation, Row as NeoRow, UnboundedRelation as NeoUnboundedRelation,
 };
 
+pub use crate::spatial::Crs as NeoCrs;
 pub use crate::stream::RowStream as NeoRowStream;
 pub use crate::txn::Txn as NeoTxn;
 pub use crate::version::Version as NeoVersion;
 pub use crate::Execute as NeoExecute;
-pub use neo4jrs_macros::Label as NeoLabel;
+pub use neo4jrs_macros::{
+    Label as NeoLabel, Properties as NeoProperties, Relationship as NeoRelationship,
+    Value as NeoValue,
+};
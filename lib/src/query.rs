@@ -8,7 +8,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 /// Abstracts a cypher query that is sent to neo4j server.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Query {
     query: String,
     params: BoltMap,
@@ -34,6 +34,25 @@ impl Query {
         self
     }
 
+    /// Replaces the placeholder, e.g. `$label`, with the label quoted by
+    /// [`escape_identifier`](crate::escape_identifier), since labels, relationship types and
+    /// property keys can't be parameters. Placeholders in strings and quoted names are left as is,
+    /// as are those followed by more of a name, e.g. `$labels` for `$label`.
+    ///
+    /// ```
+    /// use neo4jrs::Query;
+    ///
+    /// let query = Query::new("match (n:$label) return n")
+    ///     .with_label("$label", "Person) detach delete (n")
+    ///     .unwrap();
+    /// assert_eq!(query, Query::new("match (n:`Person) detach delete (n`) return n"));
+    /// ```
+    pub fn with_label(mut self, placeholder: &str, label: &str) -> Result<Self> {
+        let label = escape_identifier(label)?;
+        self.query = replace_placeholder(&self.query, placeholder, &label);
+        Ok(self)
+    }
+
     pub(crate) async fn run(
         self,
         config: &Config,
@@ -73,3 +92,90 @@ impl Query {
         }
     }
 }
+
+/// Replaces the placeholder outside strings and quoted names
+fn replace_placeholder(query: &str, placeholder: &str, replacement: &str) -> String {
+    let mut replaced = String::with_capacity(query.len());
+    let mut quote = None;
+    let mut escaped = false;
+    let mut rest = query;
+    while let Some(c) = rest.chars().next() {
+        if quote.is_none() && !placeholder.is_empty() && rest.starts_with(placeholder) {
+            let after = &rest[placeholder.len()..];
+            if !after.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+                replaced.push_str(replacement);
+                rest = after;
+                continue;
+            }
+        }
+        match quote {
+            None if c == '\'' || c == '"' || c == '`' => quote = Some(c),
+            Some(_) if escaped => escaped = false,
+            Some(q) if c == '\\' && q != '`' => escaped = true,
+            Some(q) if c == q => quote = None,
+            _ => {}
+        }
+        replaced.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    replaced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_quote_labels() {
+        let query = Query::new("match (n:$label)-[:$type]->(m:$label) return n.$key, $labels")
+            .with_label("$label", "Café")
+            .and_then(|query| query.with_label("$type", "MATCH"))
+            .and_then(|query| query.with_label("$key", "first_name"))
+            .unwrap();
+
+        assert_eq!(
+            query,
+            Query::new("match (n:`Café`)-[:`MATCH`]->(m:`Café`) return n.first_name, $labels")
+        );
+    }
+
+    #[test]
+    fn should_escape_backticks() {
+        let query = Query::new("match (n:$label) return n")
+            .with_label("$label", "a`) detach delete (n) //`")
+            .unwrap();
+
+        assert_eq!(
+            query,
+            Query::new("match (n:`a``) detach delete (n) //```) return n")
+        );
+    }
+
+    #[test]
+    fn should_leave_strings_and_quoted_names() {
+        let query = Query::new(
+            r#"match (n:$label {note: '$label \' $label', other: "$label"}) return n.`$label`"#,
+        )
+        .with_label("$label", "Person")
+        .unwrap();
+
+        assert_eq!(
+            query,
+            Query::new(
+                r#"match (n:Person {note: '$label \' $label', other: "$label"}) return n.`$label`"#
+            )
+        );
+    }
+
+    #[test]
+    fn should_refuse_invalid_labels() {
+        assert!(matches!(
+            Query::new("match (n:$label) return n").with_label("$label", ""),
+            Err(Error::InvalidIdentifier(_))
+        ));
+        assert!(matches!(
+            Query::new("match (n:$label) return n").with_label("$label", "a\0b"),
+            Err(Error::InvalidIdentifier(_))
+        ));
+    }
+}
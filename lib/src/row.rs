@@ -34,11 +34,13 @@ pub struct UnboundedRelation {
 }
 
 /// Represents a single location in 2-dimensional space
+#[derive(Debug, Clone, PartialEq)]
 pub struct Point2D {
     inner: BoltPoint2D,
 }
 
 /// Represents a single location in 3-dimensional space
+#[derive(Debug, Clone, PartialEq)]
 pub struct Point3D {
     inner: BoltPoint3D,
 }
@@ -83,6 +85,12 @@ impl Point2D {
     }
 }
 
+impl Into<BoltType> for Point2D {
+    fn into(self) -> BoltType {
+        BoltType::Point2D(self.inner)
+    }
+}
+
 impl Point3D {
     pub fn new(inner: BoltPoint3D) -> Self {
         Point3D { inner }
@@ -106,6 +114,12 @@ impl Point3D {
     }
 }
 
+impl Into<BoltType> for Point3D {
+    fn into(self) -> BoltType {
+        BoltType::Point3D(self.inner)
+    }
+}
+
 impl Row {
     pub fn new(fields: BoltList, data: BoltList) -> Self {
         let mut attributes = BoltMap::with_capacity(fields.len());
@@ -120,6 +134,22 @@ impl Row {
     pub fn get<T: std::convert::TryFrom<BoltType>>(&self, key: &str) -> Option<T> {
         self.attributes.get(key)
     }
+
+    /// Converts the row into a json object keyed by the returned fields
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> crate::errors::Result<serde_json::Value> {
+        std::convert::TryFrom::try_from(self.attributes.clone())
+    }
+}
+
+#[cfg(feature = "json")]
+impl std::convert::TryFrom<serde_json::Value> for Row {
+    type Error = crate::errors::Error;
+
+    fn try_from(input: serde_json::Value) -> crate::errors::Result<Row> {
+        let attributes = std::convert::TryFrom::try_from(input)?;
+        Ok(Row { attributes })
+    }
 }
 
 impl Node {
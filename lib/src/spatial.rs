@@ -0,0 +1,316 @@
+use crate::errors::*;
+use crate::row::{Point2D, Point3D};
+use crate::types::*;
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+
+/// Equatorial earth radius used by neo4j when computing geographic distances
+const EARTH_RADIUS_METERS: f64 = 6_378_140.0;
+
+/// Coordinate reference systems supported by neo4j, see
+/// <https://neo4j.com/docs/cypher-manual/current/values-and-types/spatial/#spatial-values-crs>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Crs {
+    /// Geographic 2d points, `x` is the longitude and `y` the latitude in degrees
+    Wgs84,
+    /// Geographic 3d points, `x` is the longitude and `y` the latitude in degrees and `z` the
+    /// height in meters
+    Wgs843D,
+    /// Euclidean 2d points
+    Cartesian,
+    /// Euclidean 3d points
+    Cartesian3D,
+}
+
+impl Crs {
+    /// Spatial reference system identifier, see <https://en.wikipedia.org/wiki/Spatial_reference_system#Identifier>
+    pub fn srid(&self) -> i64 {
+        match self {
+            Crs::Wgs84 => 4326,
+            Crs::Wgs843D => 4979,
+            Crs::Cartesian => 7203,
+            Crs::Cartesian3D => 9157,
+        }
+    }
+
+    pub fn from_srid(srid: i64) -> Option<Crs> {
+        match srid {
+            4326 => Some(Crs::Wgs84),
+            4979 => Some(Crs::Wgs843D),
+            7203 => Some(Crs::Cartesian),
+            9157 => Some(Crs::Cartesian3D),
+            _ => None,
+        }
+    }
+
+    /// Name of the crs in cypher, e.g. `point({x: 1, y: 2, crs: 'cartesian'})`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Crs::Wgs84 => "wgs-84",
+            Crs::Wgs843D => "wgs-84-3d",
+            Crs::Cartesian => "cartesian",
+            Crs::Cartesian3D => "cartesian-3d",
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        match self {
+            Crs::Wgs84 | Crs::Cartesian => 2,
+            Crs::Wgs843D | Crs::Cartesian3D => 3,
+        }
+    }
+
+    pub fn is_geographic(&self) -> bool {
+        matches!(self, Crs::Wgs84 | Crs::Wgs843D)
+    }
+
+    /// Distance between two coordinates of this crs, computed the same way as `point.distance`
+    /// in cypher: euclidean for cartesian points, haversine (in meters) for geographic points.
+    /// For 3d geographic points the arc is taken at their mean height above the earth radius and
+    /// combined with the height difference.
+    fn distance(&self, a: &[f64], b: &[f64]) -> f64 {
+        if !self.is_geographic() {
+            return a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>()
+                .sqrt();
+        }
+
+        let (lat1, lat2) = (a[1].to_radians(), b[1].to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (b[0] - a[0]).to_radians();
+        let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        let angle = 2.0 * h.sqrt().atan2((1.0 - h).sqrt());
+        match (a.get(2), b.get(2)) {
+            (Some(z1), Some(z2)) => {
+                let arc = (EARTH_RADIUS_METERS + (z1 + z2) / 2.0) * angle;
+                (arc.powi(2) + (z2 - z1).powi(2)).sqrt()
+            }
+            _ => EARTH_RADIUS_METERS * angle,
+        }
+    }
+}
+
+impl Display for Crs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl TryFrom<i64> for Crs {
+    type Error = Error;
+
+    fn try_from(srid: i64) -> Result<Crs> {
+        Crs::from_srid(srid).ok_or(Error::ConverstionError)
+    }
+}
+
+impl Point2D {
+    /// Creates a point in the given crs
+    ///
+    /// # Panics
+    ///
+    /// Panics if the crs is 3-dimensional.
+    pub fn with_crs(crs: Crs, x: f64, y: f64) -> Self {
+        assert_eq!(crs.dimension(), 2, "{} is not a 2d crs", crs);
+        Point2D::new(BoltPoint2D {
+            sr_id: crs.srid().into(),
+            x: BoltFloat::new(x),
+            y: BoltFloat::new(y),
+        })
+    }
+
+    pub fn cartesian(x: f64, y: f64) -> Self {
+        Point2D::with_crs(Crs::Cartesian, x, y)
+    }
+
+    pub fn wgs84(longitude: f64, latitude: f64) -> Self {
+        Point2D::with_crs(Crs::Wgs84, longitude, latitude)
+    }
+
+    /// The crs of the point, `None` if the srid isn't one supported by neo4j
+    pub fn crs(&self) -> Option<Crs> {
+        Crs::from_srid(self.sr_id())
+    }
+
+    /// Same as `point.distance` in cypher, `None` if the points are in different crs
+    pub fn distance(&self, other: &Point2D) -> Option<f64> {
+        let crs = self.crs().filter(|&crs| Some(crs) == other.crs())?;
+        Some(crs.distance(&[self.x(), self.y()], &[other.x(), other.y()]))
+    }
+}
+
+impl Point3D {
+    /// Creates a point in the given crs
+    ///
+    /// # Panics
+    ///
+    /// Panics if the crs is 2-dimensional.
+    pub fn with_crs(crs: Crs, x: f64, y: f64, z: f64) -> Self {
+        assert_eq!(crs.dimension(), 3, "{} is not a 3d crs", crs);
+        Point3D::new(BoltPoint3D {
+            sr_id: crs.srid().into(),
+            x: BoltFloat::new(x),
+            y: BoltFloat::new(y),
+            z: BoltFloat::new(z),
+        })
+    }
+
+    pub fn cartesian(x: f64, y: f64, z: f64) -> Self {
+        Point3D::with_crs(Crs::Cartesian3D, x, y, z)
+    }
+
+    pub fn wgs84(longitude: f64, latitude: f64, height: f64) -> Self {
+        Point3D::with_crs(Crs::Wgs843D, longitude, latitude, height)
+    }
+
+    /// The crs of the point, `None` if the srid isn't one supported by neo4j
+    pub fn crs(&self) -> Option<Crs> {
+        Crs::from_srid(self.sr_id())
+    }
+
+    /// Same as `point.distance` in cypher, `None` if the points are in different crs
+    pub fn distance(&self, other: &Point3D) -> Option<f64> {
+        let crs = self.crs().filter(|&crs| Some(crs) == other.crs())?;
+        Some(crs.distance(
+            &[self.x(), self.y(), self.z()],
+            &[other.x(), other.y(), other.z()],
+        ))
+    }
+}
+
+/// `geo_types` points have no crs, they are treated as WGS-84 longitude/latitude pairs
+#[cfg(feature = "geo")]
+impl From<geo_types::Point<f64>> for Point2D {
+    fn from(point: geo_types::Point<f64>) -> Self {
+        Point2D::wgs84(point.x(), point.y())
+    }
+}
+
+#[cfg(feature = "geo")]
+impl From<Point2D> for geo_types::Point<f64> {
+    fn from(point: Point2D) -> Self {
+        geo_types::Point::new(point.x(), point.y())
+    }
+}
+
+#[cfg(feature = "geo")]
+impl Into<BoltType> for geo_types::Point<f64> {
+    fn into(self) -> BoltType {
+        Point2D::from(self).into()
+    }
+}
+
+/// Any 2d point converts, whatever its crs
+#[cfg(feature = "geo")]
+impl TryFrom<BoltType> for geo_types::Point<f64> {
+    type Error = Error;
+
+    fn try_from(input: BoltType) -> Result<geo_types::Point<f64>> {
+        Point2D::try_from(input).map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn should_map_srids() {
+        for crs in &[Crs::Wgs84, Crs::Wgs843D, Crs::Cartesian, Crs::Cartesian3D] {
+            assert_eq!(Crs::from_srid(crs.srid()), Some(*crs));
+            assert_eq!(Crs::try_from(crs.srid()).unwrap(), *crs);
+        }
+        assert_eq!(Crs::from_srid(42), None);
+        assert_eq!(Crs::Wgs843D.to_string(), "wgs-84-3d");
+    }
+
+    #[test]
+    fn should_construct_points() {
+        let point = Point2D::wgs84(12.5, 55.7);
+        assert_eq!(point.sr_id(), 4326);
+        assert_eq!(point.crs(), Some(Crs::Wgs84));
+        assert_eq!((point.x(), point.y()), (12.5, 55.7));
+
+        let point = Point3D::cartesian(1.0, 2.0, 3.0);
+        assert_eq!(point.sr_id(), 9157);
+        assert_eq!(point.z(), 3.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "wgs-84-3d is not a 2d crs")]
+    fn should_reject_crs_with_other_dimension() {
+        Point2D::with_crs(Crs::Wgs843D, 1.0, 2.0);
+    }
+
+    #[test]
+    fn should_compute_cartesian_distances() {
+        let p1 = Point2D::cartesian(2.3, 4.5);
+        let p2 = Point2D::cartesian(1.1, 5.4);
+        assert_close(p1.distance(&p2).unwrap(), 1.5);
+
+        let p1 = Point3D::cartesian(0.0, 0.0, 0.0);
+        let p2 = Point3D::cartesian(1.0, 2.0, 2.0);
+        assert_close(p1.distance(&p2).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn should_compute_geographic_distances_like_cypher() {
+        let p1 = Point2D::wgs84(12.78, 56.7);
+        let p2 = Point2D::wgs84(12.79, 56.71);
+        assert_close(p1.distance(&p2).unwrap(), 1_269.894960563679);
+
+        let p1 = Point3D::wgs84(12.78, 56.7, 100.0);
+        let p2 = Point3D::wgs84(12.79, 56.71, 100.0);
+        assert_close(p1.distance(&p2).unwrap(), 1_269.914_870_677_909_7);
+    }
+
+    #[test]
+    fn should_not_compute_distances_across_crs() {
+        let p1 = Point2D::wgs84(1.0, 2.0);
+        let p2 = Point2D::cartesian(1.0, 2.0);
+        assert_eq!(p1.distance(&p2), None);
+    }
+
+    #[test]
+    fn should_convert_points_to_bolt_types() {
+        let bolt: BoltType = Point2D::cartesian(1.0, 2.0).into();
+        assert_eq!(
+            Point2D::try_from(bolt).unwrap(),
+            Point2D::cartesian(1.0, 2.0)
+        );
+
+        let bolt: BoltType = Point3D::wgs84(1.0, 2.0, 3.0).into();
+        assert_eq!(
+            Point3D::try_from(bolt).unwrap(),
+            Point3D::wgs84(1.0, 2.0, 3.0)
+        );
+    }
+
+    #[cfg(feature = "geo")]
+    #[test]
+    fn should_convert_geo_points() {
+        let bolt: BoltType = geo_types::Point::new(12.5, 55.7).into();
+        assert_eq!(
+            Point2D::try_from(bolt.clone()).unwrap(),
+            Point2D::wgs84(12.5, 55.7)
+        );
+
+        let point = geo_types::Point::try_from(bolt).unwrap();
+        assert_eq!(point, geo_types::Point::new(12.5, 55.7));
+
+        let bolt: BoltType = Point3D::wgs84(1.0, 2.0, 3.0).into();
+        assert!(geo_types::Point::<f64>::try_from(bolt).is_err());
+    }
+}
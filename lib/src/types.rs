@@ -2,6 +2,7 @@ pub mod binary;
 pub mod boolean;
 pub mod date;
 pub mod date_time;
+mod display;
 pub mod duration;
 pub mod float;
 pub mod integer;
@@ -9,6 +10,7 @@ pub mod list;
 pub mod map;
 pub mod node;
 pub mod null;
+mod order;
 pub mod path;
 pub mod point;
 pub mod relation;
@@ -17,7 +19,11 @@ pub mod time;
 pub use binary::BoltBytes;
 pub use boolean::BoltBoolean;
 pub use date::BoltDate;
-pub use date_time::{BoltDateTime, BoltDateTimeZoneId, BoltLocalDateTime};
+pub use date_time::{BoltDateTime, BoltDateTimeZoneId, BoltDateTimeZoneIdUtc, BoltLocalDateTime};
+pub use display::escape_identifier;
+#[cfg(feature = "json")]
+pub(crate) use display::iso_8601;
+pub(crate) use display::write_identifier;
 pub use duration::BoltDuration;
 pub use float::BoltFloat;
 pub use integer::BoltInteger;
@@ -35,10 +41,9 @@ use crate::errors::*;
 use crate::version::Version;
 use bytes::Bytes;
 use std::cell::RefCell;
-use std::fmt::Display;
 use std::rc::Rc;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum BoltType {
     String(BoltString),
     Boolean(BoltBoolean),
@@ -61,16 +66,7 @@ pub enum BoltType {
     DateTime(BoltDateTime),
     LocalDateTime(BoltLocalDateTime),
     DateTimeZoneId(BoltDateTimeZoneId),
-}
-
-impl Display for BoltType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let value = match self {
-            BoltType::String(s) => s.to_string(),
-            _ => "to_string not implemented".to_owned(),
-        };
-        write!(f, "{}", value)
-    }
+    DateTimeZoneIdUtc(BoltDateTimeZoneIdUtc),
 }
 
 impl BoltType {
@@ -97,6 +93,7 @@ impl BoltType {
             BoltType::DateTime(t) => t.into_bytes(version),
             BoltType::LocalDateTime(t) => t.into_bytes(version),
             BoltType::DateTimeZoneId(t) => t.into_bytes(version),
+            BoltType::DateTimeZoneIdUtc(t) => t.into_bytes(version),
         }
     }
 
@@ -159,6 +156,9 @@ impl BoltType {
             input if BoltDateTimeZoneId::can_parse(version, input.clone()) => {
                 BoltType::DateTimeZoneId(BoltDateTimeZoneId::parse(version, input)?)
             }
+            input if BoltDateTimeZoneIdUtc::can_parse(version, input.clone()) => {
+                BoltType::DateTimeZoneIdUtc(BoltDateTimeZoneIdUtc::parse(version, input)?)
+            }
             input if BoltUnboundedRelation::can_parse(version, input.clone()) => {
                 BoltType::UnboundedRelation(BoltUnboundedRelation::parse(version, input)?)
             }
@@ -1,18 +1,18 @@
 use crate::errors::Error;
 use crate::types::*;
-use chrono::{Duration, NaiveDate};
+use chrono::{DateTime, Duration, NaiveDate};
 use neo4jrs_macros::BoltStruct;
 use std::convert::TryInto;
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB1, 0x44)]
 pub struct BoltDate {
-    days: BoltInteger,
+    pub(crate) days: BoltInteger,
 }
 
 impl Into<BoltDate> for NaiveDate {
     fn into(self) -> BoltDate {
-        let epoch = NaiveDate::from_ymd(1970, 1, 1);
+        let epoch = DateTime::UNIX_EPOCH.date_naive();
         let days = (self - epoch).num_days().into();
         BoltDate { days }
     }
@@ -22,14 +22,40 @@ impl TryInto<NaiveDate> for BoltDate {
     type Error = Error;
 
     fn try_into(self) -> Result<NaiveDate> {
-        let epoch = NaiveDate::from_ymd(1970, 1, 1);
-        let days = Duration::days(self.days.value);
+        let epoch = DateTime::UNIX_EPOCH.date_naive();
+        let days = Duration::try_days(self.days.value).ok_or(Error::ConverstionError)?;
         epoch
             .checked_add_signed(days)
             .ok_or(Error::ConverstionError)
     }
 }
 
+#[cfg(feature = "time")]
+const UNIX_EPOCH_JULIAN_DAY: i64 = 2_440_588;
+
+#[cfg(feature = "time")]
+impl Into<BoltDate> for ::time::Date {
+    fn into(self) -> BoltDate {
+        let days = (self.to_julian_day() as i64 - UNIX_EPOCH_JULIAN_DAY).into();
+        BoltDate { days }
+    }
+}
+
+/// The `time` crate conversions are inherent methods rather than `TryInto` impls, so that enabling
+/// the feature doesn't make `try_into()` into the chrono types ambiguous.
+#[cfg(feature = "time")]
+impl BoltDate {
+    pub fn try_into_time(self) -> Result<::time::Date> {
+        let julian_day = self
+            .days
+            .value
+            .checked_add(UNIX_EPOCH_JULIAN_DAY)
+            .and_then(|day| std::convert::TryFrom::try_from(day).ok())
+            .ok_or(Error::ConverstionError)?;
+        ::time::Date::from_julian_day(julian_day).map_err(|_| Error::ConverstionError)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,7 +66,7 @@ mod tests {
 
     #[test]
     fn should_serialize_a_date() {
-        let date: BoltDate = NaiveDate::from_ymd(2010, 1, 1).into();
+        let date: BoltDate = NaiveDate::from_ymd_opt(2010, 1, 1).unwrap().into();
         assert_eq!(
             date.into_bytes(Version::V4_1).unwrap(),
             Bytes::from_static(&[0xB1, 0x44, 0xC9, 0x39, 0x12])
@@ -60,4 +86,37 @@ mod tests {
 
         assert_eq!(date.to_string(), "2010-01-01");
     }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn should_convert_time_dates() {
+        use ::time::{Date, Month};
+
+        let date: BoltDate = Date::from_calendar_date(2010, Month::January, 1)
+            .unwrap()
+            .into();
+        assert_eq!(
+            date.clone().into_bytes(Version::V4_1).unwrap(),
+            Bytes::from_static(&[0xB1, 0x44, 0xC9, 0x39, 0x12])
+        );
+        let date = date.try_into_time().unwrap();
+        assert_eq!(date.to_string(), "2010-01-01");
+
+        for date in &[
+            Date::from_calendar_date(1969, Month::December, 31).unwrap(),
+            Date::from_calendar_date(1970, Month::January, 1).unwrap(),
+            Date::MIN,
+            Date::MAX,
+        ] {
+            let bolt: BoltDate = (*date).into();
+            let converted = bolt.try_into_time().unwrap();
+            assert_eq!(&converted, date);
+        }
+
+        let out_of_range = BoltDate {
+            days: i64::MAX.into(),
+        }
+        .try_into_time();
+        assert!(out_of_range.is_err());
+    }
 }
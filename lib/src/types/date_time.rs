@@ -4,33 +4,44 @@ use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, Timelike};
 use neo4jrs_macros::BoltStruct;
 use std::convert::TryInto;
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB3, 0x46)]
 pub struct BoltDateTime {
-    seconds: BoltInteger,
-    nanoseconds: BoltInteger,
-    tz_offset_seconds: BoltInteger,
+    pub(crate) seconds: BoltInteger,
+    pub(crate) nanoseconds: BoltInteger,
+    pub(crate) tz_offset_seconds: BoltInteger,
 }
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB2, 0x64)]
 pub struct BoltLocalDateTime {
-    seconds: BoltInteger,
-    nanoseconds: BoltInteger,
+    pub(crate) seconds: BoltInteger,
+    pub(crate) nanoseconds: BoltInteger,
 }
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB3, 0x66)]
 pub struct BoltDateTimeZoneId {
-    seconds: BoltInteger,
-    nanoseconds: BoltInteger,
-    tz_id: BoltString,
+    pub(crate) seconds: BoltInteger,
+    pub(crate) nanoseconds: BoltInteger,
+    pub(crate) tz_id: BoltString,
+}
+
+/// A datetime with a zone id, where unlike [`BoltDateTimeZoneId`] the seconds are counted from the
+/// epoch in UTC rather than in the local time of the zone, as sent by servers speaking bolt 5
+/// (or bolt 4.4 with the `utc` patch).
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
+#[signature(0xB3, 0x69)]
+pub struct BoltDateTimeZoneIdUtc {
+    pub(crate) seconds: BoltInteger,
+    pub(crate) nanoseconds: BoltInteger,
+    pub(crate) tz_id: BoltString,
 }
 
 impl Into<BoltDateTimeZoneId> for (NaiveDateTime, &str) {
     fn into(self) -> BoltDateTimeZoneId {
-        let seconds = self.0.timestamp().into();
-        let nanoseconds = (self.0.timestamp_subsec_nanos() as i64).into();
+        let seconds = self.0.and_utc().timestamp().into();
+        let nanoseconds = (self.0.and_utc().timestamp_subsec_nanos() as i64).into();
         BoltDateTimeZoneId {
             seconds,
             nanoseconds,
@@ -43,15 +54,93 @@ impl TryInto<(NaiveDateTime, String)> for BoltDateTimeZoneId {
     type Error = Error;
 
     fn try_into(self) -> Result<(NaiveDateTime, String)> {
-        let datetime =
-            NaiveDateTime::from_timestamp(self.seconds.value, self.nanoseconds.value as u32);
+        let datetime = DateTime::from_timestamp(self.seconds.value, self.nanoseconds.value as u32)
+            .ok_or(Error::ConverstionError)?
+            .naive_utc();
+        Ok((datetime, self.tz_id.into()))
+    }
+}
+
+#[cfg(feature = "tz")]
+impl Into<BoltDateTimeZoneId> for DateTime<chrono_tz::Tz> {
+    fn into(self) -> BoltDateTimeZoneId {
+        (self.naive_local(), self.timezone().name()).into()
+    }
+}
+
+#[cfg(feature = "tz")]
+impl TryInto<DateTime<chrono_tz::Tz>> for BoltDateTimeZoneId {
+    type Error = Error;
+
+    /// The seconds are in local time, which is ambiguous when the clocks go back and doesn't
+    /// exist when they go forward. Like the server, the earlier offset is picked for the former
+    /// and the time is moved forward by the length of the gap for the latter.
+    fn try_into(self) -> Result<DateTime<chrono_tz::Tz>> {
+        use chrono::{LocalResult, TimeZone};
+
+        let zone = parse_zone(&self.tz_id.value)?;
+        let (local, _): (NaiveDateTime, String) = self.try_into()?;
+        match zone.from_local_datetime(&local) {
+            LocalResult::Single(datetime) => Ok(datetime),
+            LocalResult::Ambiguous(earliest, _) => Ok(earliest),
+            LocalResult::None => {
+                let before = local
+                    .checked_sub_signed(chrono::TimeDelta::days(1))
+                    .ok_or(Error::ConverstionError)?;
+                let offset = zone.offset_from_local_datetime(&before).earliest();
+                let offset = offset.ok_or(Error::ConverstionError)?.fix();
+                let utc = local
+                    .checked_sub_signed(chrono::TimeDelta::seconds(offset.local_minus_utc().into()))
+                    .ok_or(Error::ConverstionError)?;
+                Ok(zone.from_utc_datetime(&utc))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tz")]
+impl Into<BoltDateTimeZoneIdUtc> for DateTime<chrono_tz::Tz> {
+    fn into(self) -> BoltDateTimeZoneIdUtc {
+        BoltDateTimeZoneIdUtc {
+            seconds: self.timestamp().into(),
+            nanoseconds: (self.timestamp_subsec_nanos() as i64).into(),
+            tz_id: self.timezone().name().into(),
+        }
+    }
+}
+
+#[cfg(feature = "tz")]
+impl TryInto<DateTime<chrono_tz::Tz>> for BoltDateTimeZoneIdUtc {
+    type Error = Error;
+
+    fn try_into(self) -> Result<DateTime<chrono_tz::Tz>> {
+        let zone = parse_zone(&self.tz_id.value)?;
+        let datetime = DateTime::from_timestamp(self.seconds.value, self.nanoseconds.value as u32)
+            .ok_or(Error::ConverstionError)?;
+        Ok(datetime.with_timezone(&zone))
+    }
+}
+
+impl TryInto<(DateTime<chrono::Utc>, String)> for BoltDateTimeZoneIdUtc {
+    type Error = Error;
+
+    fn try_into(self) -> Result<(DateTime<chrono::Utc>, String)> {
+        let datetime = DateTime::from_timestamp(self.seconds.value, self.nanoseconds.value as u32)
+            .ok_or(Error::ConverstionError)?;
         Ok((datetime, self.tz_id.into()))
     }
 }
 
+#[cfg(feature = "tz")]
+fn parse_zone(tz_id: &str) -> Result<chrono_tz::Tz> {
+    tz_id
+        .parse()
+        .map_err(|_| Error::DeserializationError(format!("unknown time zone `{}`", tz_id)))
+}
+
 impl Into<BoltLocalDateTime> for NaiveDateTime {
     fn into(self) -> BoltLocalDateTime {
-        let seconds = self.timestamp().into();
+        let seconds = self.and_utc().timestamp().into();
         let nanoseconds = (self.nanosecond() as i64).into();
 
         BoltLocalDateTime {
@@ -65,10 +154,9 @@ impl TryInto<NaiveDateTime> for BoltLocalDateTime {
     type Error = Error;
 
     fn try_into(self) -> Result<NaiveDateTime> {
-        Ok(NaiveDateTime::from_timestamp(
-            self.seconds.value,
-            self.nanoseconds.value as u32,
-        ))
+        DateTime::from_timestamp(self.seconds.value, self.nanoseconds.value as u32)
+            .map(|datetime| datetime.naive_utc())
+            .ok_or(Error::ConverstionError)
     }
 }
 
@@ -91,14 +179,78 @@ impl TryInto<DateTime<FixedOffset>> for BoltDateTime {
 
     fn try_into(self) -> Result<DateTime<FixedOffset>> {
         let seconds = self.seconds.value - self.tz_offset_seconds.value;
-        let datetime = NaiveDateTime::from_timestamp(seconds, self.nanoseconds.value as u32);
-        Ok(DateTime::from_utc(
-            datetime,
-            FixedOffset::east(self.tz_offset_seconds.value as i32),
+        let datetime = DateTime::from_timestamp(seconds, self.nanoseconds.value as u32)
+            .ok_or(Error::ConverstionError)?;
+        let offset = FixedOffset::east_opt(self.tz_offset_seconds.value as i32)
+            .ok_or(Error::ConverstionError)?;
+        Ok(datetime.with_timezone(&offset))
+    }
+}
+
+#[cfg(feature = "time")]
+impl Into<BoltLocalDateTime> for ::time::PrimitiveDateTime {
+    fn into(self) -> BoltLocalDateTime {
+        BoltLocalDateTime {
+            seconds: self.assume_utc().unix_timestamp().into(),
+            nanoseconds: (self.nanosecond() as i64).into(),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl BoltLocalDateTime {
+    pub fn try_into_time(self) -> Result<::time::PrimitiveDateTime> {
+        let datetime = offset_datetime_from_timestamp(self.seconds.value, self.nanoseconds.value)?;
+        Ok(::time::PrimitiveDateTime::new(
+            datetime.date(),
+            datetime.time(),
         ))
     }
 }
 
+#[cfg(feature = "time")]
+impl Into<BoltDateTime> for ::time::OffsetDateTime {
+    fn into(self) -> BoltDateTime {
+        let offset = self.offset().whole_seconds() as i64;
+        BoltDateTime {
+            seconds: (self.unix_timestamp() + offset).into(),
+            nanoseconds: (self.nanosecond() as i64).into(),
+            tz_offset_seconds: offset.into(),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl BoltDateTime {
+    pub fn try_into_time(self) -> Result<::time::OffsetDateTime> {
+        let offset = std::convert::TryFrom::try_from(self.tz_offset_seconds.value)
+            .ok()
+            .and_then(|seconds| ::time::UtcOffset::from_whole_seconds(seconds).ok())
+            .ok_or(Error::ConverstionError)?;
+        let seconds = self
+            .seconds
+            .value
+            .checked_sub(self.tz_offset_seconds.value)
+            .ok_or(Error::ConverstionError)?;
+        offset_datetime_from_timestamp(seconds, self.nanoseconds.value)?
+            .checked_to_offset(offset)
+            .ok_or(Error::ConverstionError)
+    }
+}
+
+/// Leap seconds can't be represented by the `time` crate and fail to convert
+#[cfg(feature = "time")]
+fn offset_datetime_from_timestamp(
+    seconds: i64,
+    nanoseconds: i64,
+) -> Result<::time::OffsetDateTime> {
+    let nanoseconds =
+        std::convert::TryFrom::try_from(nanoseconds).map_err(|_| Error::ConverstionError)?;
+    ::time::OffsetDateTime::from_unix_timestamp(seconds)
+        .and_then(|datetime| datetime.replace_nanosecond(nanoseconds))
+        .map_err(|_| Error::ConverstionError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,4 +347,174 @@ mod tests {
         assert_eq!(datetime.to_string(), "2015-07-01 08:59:60.123");
         assert_eq!(zone_id, "Europe/Paris");
     }
+
+    #[test]
+    fn should_deserialize_a_utc_datetime_with_zoneid() {
+        let bytes = Rc::new(RefCell::new(Bytes::from_static(&[
+            0xB3, 0x69, 0xCA, 0x55, 0x93, 0x9F, 0xFF, 0xCA, 0x07, 0x54, 0xD4, 0xC0, 0x8C, 0x45,
+            0x75, 0x72, 0x6F, 0x70, 0x65, 0x2F, 0x50, 0x61, 0x72, 0x69, 0x73,
+        ])));
+
+        let (datetime, zone_id): (DateTime<chrono::Utc>, String) =
+            BoltDateTimeZoneIdUtc::parse(Version::V4_1, bytes)
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        assert_eq!(datetime.to_rfc3339(), "2015-07-01T08:08:31.123+00:00");
+        assert_eq!(zone_id, "Europe/Paris");
+    }
+
+    #[cfg(feature = "tz")]
+    mod tz {
+        use super::*;
+        use chrono::TimeZone;
+        use chrono_tz::{Europe::Paris, Tz};
+
+        fn local(datetime: &str) -> BoltDateTimeZoneId {
+            let datetime = NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S").unwrap();
+            (datetime, "Europe/Paris").into()
+        }
+
+        #[test]
+        fn should_convert_a_zoned_datetime_using_local_seconds() {
+            let datetime = Paris.with_ymd_and_hms(2021, 7, 1, 10, 0, 0).unwrap();
+
+            let bolt: BoltDateTimeZoneId = datetime.into();
+
+            assert_eq!(bolt, local("2021-07-01 10:00:00"));
+            let converted: DateTime<Tz> = bolt.try_into().unwrap();
+            assert_eq!(converted, datetime);
+        }
+
+        #[test]
+        fn should_convert_a_zoned_datetime_using_utc_seconds() {
+            let datetime = Paris.with_ymd_and_hms(2021, 7, 1, 10, 0, 0).unwrap();
+
+            let bolt: BoltDateTimeZoneIdUtc = datetime.into();
+
+            assert_eq!(bolt.seconds.value, datetime.timestamp());
+            let converted: DateTime<Tz> = bolt.try_into().unwrap();
+            assert_eq!(converted, datetime);
+        }
+
+        #[test]
+        fn should_pick_the_earlier_offset_for_ambiguous_local_times() {
+            let datetime: DateTime<Tz> = local("2021-10-31 02:30:00").try_into().unwrap();
+
+            assert_eq!(datetime.to_rfc3339(), "2021-10-31T02:30:00+02:00");
+        }
+
+        #[test]
+        fn should_shift_local_times_in_a_gap_forward() {
+            let datetime: DateTime<Tz> = local("2021-03-28 02:30:00").try_into().unwrap();
+
+            assert_eq!(datetime.to_rfc3339(), "2021-03-28T03:30:00+02:00");
+        }
+
+        #[test]
+        fn should_keep_the_instant_of_utc_datetimes_across_transitions() {
+            let later = Paris
+                .from_local_datetime(
+                    &NaiveDateTime::parse_from_str("2021-10-31 02:30:00", "%Y-%m-%d %H:%M:%S")
+                        .unwrap(),
+                )
+                .latest()
+                .unwrap();
+
+            let bolt: BoltDateTimeZoneIdUtc = later.into();
+            let converted: DateTime<Tz> = bolt.try_into().unwrap();
+
+            assert_eq!(converted.to_rfc3339(), "2021-10-31T02:30:00+01:00");
+        }
+
+        #[test]
+        fn should_reject_unknown_zones() {
+            let datetime =
+                NaiveDateTime::parse_from_str("2021-07-01 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+            let bolt: BoltDateTimeZoneId = (datetime, "Mars/Olympus_Mons").into();
+
+            let result: Result<DateTime<Tz>> = bolt.try_into();
+
+            assert!(matches!(result, Err(Error::DeserializationError(_))));
+        }
+    }
+
+    #[cfg(feature = "time")]
+    mod time_crate {
+        use super::*;
+        use ::time::{Date, Month, PrimitiveDateTime, Time, UtcOffset};
+
+        fn datetime(year: i32, month: Month, day: u8, time: Time) -> PrimitiveDateTime {
+            PrimitiveDateTime::new(Date::from_calendar_date(year, month, day).unwrap(), time)
+        }
+
+        #[test]
+        fn should_convert_offset_datetimes_using_local_seconds() {
+            let offset = UtcOffset::from_hms(1, 0, 0).unwrap();
+            let datetime = datetime(2015, Month::June, 24, Time::from_hms(12, 50, 35).unwrap())
+                .assume_offset(offset);
+
+            let bolt: BoltDateTime = datetime.into();
+
+            assert_eq!(
+                bolt.clone().into_bytes(Version::V4_1).unwrap(),
+                Bytes::from_static(&[
+                    0xB3, 0x46, 0xCA, 0x55, 0x8A, 0xA7, 0x9B, 0x00, 0xC9, 0x0E, 0x10,
+                ])
+            );
+            let converted = bolt.try_into_time().unwrap();
+            assert_eq!(converted, datetime);
+            assert_eq!(converted.offset(), offset);
+        }
+
+        #[test]
+        fn should_round_trip_at_nanosecond_boundaries() {
+            let times = [
+                Time::MIDNIGHT,
+                Time::from_hms_nano(0, 0, 0, 1).unwrap(),
+                Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap(),
+            ];
+            let dates = [
+                (1969, Month::December, 31),
+                (1970, Month::January, 1),
+                (2262, Month::April, 11),
+            ];
+            let offsets = [
+                UtcOffset::UTC,
+                UtcOffset::from_hms(-5, -30, 0).unwrap(),
+                UtcOffset::from_hms(14, 0, 0).unwrap(),
+            ];
+
+            for &(year, month, day) in &dates {
+                for time in &times {
+                    let local = datetime(year, month, day, *time);
+                    let bolt: BoltLocalDateTime = local.into();
+                    let converted = bolt.try_into_time().unwrap();
+                    assert_eq!(converted, local);
+
+                    for offset in &offsets {
+                        let zoned = local.assume_offset(*offset);
+                        let bolt: BoltDateTime = zoned.into();
+                        let converted = bolt.try_into_time().unwrap();
+                        assert_eq!(converted, zoned);
+                        assert_eq!(converted.offset(), *offset);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn should_reject_leap_seconds() {
+            let bytes = Rc::new(RefCell::new(Bytes::from_static(&[
+                0xB2, 0x64, 0xCA, 0x55, 0x93, 0xAC, 0x0F, 0xCA, 0x42, 0xEF, 0x9E, 0xC0,
+            ])));
+
+            let converted = BoltLocalDateTime::parse(Version::V4_1, bytes)
+                .unwrap()
+                .try_into_time();
+
+            assert!(converted.is_err());
+        }
+    }
 }
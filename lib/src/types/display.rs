@@ -0,0 +1,596 @@
+use crate::errors::*;
+use crate::types::*;
+use crate::{Crs, Duration};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Utc};
+use std::convert::{TryFrom, TryInto};
+use std::fmt::{self, Display, Formatter, Write};
+
+/// Renders values the way cypher does, e.g. `[1, "a", {name: "Mark"}]` or
+/// `(:Person {name: "Mark"})-[:KNOWS]->(:Person {name: "James"})`.
+///
+/// A string is rendered as is, unless it is nested within a list, map or entity in which case
+/// it is quoted.
+impl Display for BoltType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BoltType::String(s) => f.write_str(&s.value),
+            value => write_value(f, value),
+        }
+    }
+}
+
+impl BoltType {
+    /// Renders the value as a cypher expression evaluating to the same value, e.g. the string
+    /// `it's` becomes `'it\'s'` and a date becomes `date('2010-01-01')`.
+    ///
+    /// Bytes, nodes, relationships and paths have no literal form and fail with a
+    /// [`Error::ConverstionError`].
+    pub fn to_cypher_literal(&self) -> Result<String> {
+        let mut literal = String::new();
+        write_literal(&mut literal, self)?;
+        Ok(literal)
+    }
+}
+
+fn write_value(f: &mut Formatter<'_>, value: &BoltType) -> fmt::Result {
+    match value {
+        BoltType::Null(_) => f.write_str("null"),
+        BoltType::Boolean(b) => write!(f, "{}", b.value),
+        BoltType::Integer(i) => write!(f, "{}", i.value),
+        BoltType::Float(x) => match x.value {
+            x if x.is_nan() => f.write_str("NaN"),
+            x if x.is_infinite() && x > 0.0 => f.write_str("Infinity"),
+            x if x.is_infinite() => f.write_str("-Infinity"),
+            x => write!(f, "{:?}", x),
+        },
+        BoltType::String(s) => write_quoted(f, &s.value, '"'),
+        BoltType::Bytes(b) => {
+            f.write_str("0x")?;
+            b.value.iter().try_for_each(|b| write!(f, "{:02x}", b))
+        }
+        BoltType::List(l) => {
+            f.write_char('[')?;
+            for (i, value) in l.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write_value(f, value)?;
+            }
+            f.write_char(']')
+        }
+        BoltType::Map(m) => write_map(f, m),
+        BoltType::Node(n) => write_node(f, n),
+        BoltType::Relation(r) => write_relation(f, &r.typ, &r.properties),
+        BoltType::UnboundedRelation(r) => write_relation(f, &r.typ, &r.properties),
+        BoltType::Path(p) => write_path(f, p),
+        BoltType::Point2D(p) => write_point(f, p.sr_id.value, &[p.x.value, p.y.value]),
+        BoltType::Point3D(p) => write_point(f, p.sr_id.value, &[p.x.value, p.y.value, p.z.value]),
+        temporal => match iso_8601(temporal) {
+            Ok(iso) => f.write_str(&iso),
+            Err(_) => write!(f, "{:?}", temporal),
+        },
+    }
+}
+
+fn write_map(f: &mut Formatter<'_>, map: &BoltMap) -> fmt::Result {
+    f.write_char('{')?;
+    for (i, (key, value)) in map.value.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}: ", key.value)?;
+        write_value(f, value)?;
+    }
+    f.write_char('}')
+}
+
+fn write_node(f: &mut Formatter<'_>, node: &BoltNode) -> fmt::Result {
+    f.write_char('(')?;
+    for label in node.labels.iter() {
+        write!(f, ":{}", label)?;
+    }
+    if !node.properties.is_empty() {
+        if !node.labels.is_empty() {
+            f.write_char(' ')?;
+        }
+        write_map(f, &node.properties)?;
+    }
+    f.write_char(')')
+}
+
+fn write_relation(f: &mut Formatter<'_>, typ: &BoltString, properties: &BoltMap) -> fmt::Result {
+    write!(f, "[:{}", typ.value)?;
+    if !properties.is_empty() {
+        f.write_char(' ')?;
+        write_map(f, properties)?;
+    }
+    f.write_char(']')
+}
+
+/// The ids of a path alternate between a relationship index, 1-based and negated when the
+/// relationship is traversed backwards, and the index of the next node.
+fn write_path(f: &mut Formatter<'_>, path: &BoltPath) -> fmt::Result {
+    let nodes = path.nodes();
+    let rels = path.rels();
+    let ids = path.ids();
+
+    match nodes.first() {
+        Some(node) => write_node(f, node)?,
+        None => return Ok(()),
+    }
+    for step in ids.chunks(2) {
+        let (rel, node) = match step {
+            [rel, node] => (rel.value, node.value),
+            _ => break,
+        };
+        let relation = rel
+            .unsigned_abs()
+            .checked_sub(1)
+            .and_then(|index| usize::try_from(index).ok())
+            .and_then(|index| rels.get(index));
+        let node = usize::try_from(node)
+            .ok()
+            .and_then(|index| nodes.get(index));
+        let (relation, node) = match (relation, node) {
+            (Some(relation), Some(node)) => (relation, node),
+            _ => break,
+        };
+        f.write_str(if rel < 0 { "<-" } else { "-" })?;
+        write_relation(f, &relation.typ, &relation.properties)?;
+        f.write_str(if rel < 0 { "-" } else { "->" })?;
+        write_node(f, node)?;
+    }
+    Ok(())
+}
+
+fn write_point(f: &mut dyn Write, srid: i64, coordinates: &[f64]) -> fmt::Result {
+    f.write_str("point({")?;
+    for (name, value) in ["x", "y", "z"].iter().zip(coordinates) {
+        write!(f, "{}: ", name)?;
+        write_float_literal(f, *value)?;
+        f.write_str(", ")?;
+    }
+    match Crs::from_srid(srid) {
+        Some(crs) => write!(f, "crs: '{}'", crs)?,
+        None => write!(f, "srid: {}", srid)?,
+    }
+    f.write_str("})")
+}
+
+fn write_literal(out: &mut String, value: &BoltType) -> Result<()> {
+    let function = match value {
+        BoltType::Null(_) => {
+            out.push_str("null");
+            return Ok(());
+        }
+        BoltType::Boolean(b) => {
+            out.push_str(if b.value { "true" } else { "false" });
+            return Ok(());
+        }
+        BoltType::Integer(i) => {
+            write!(out, "{}", i.value).unwrap();
+            return Ok(());
+        }
+        BoltType::Float(x) => {
+            write_float_literal(out, x.value).unwrap();
+            return Ok(());
+        }
+        BoltType::String(s) => {
+            write_quoted(out, &s.value, '\'').unwrap();
+            return Ok(());
+        }
+        BoltType::List(l) => {
+            out.push('[');
+            for (i, value) in l.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_literal(out, value)?;
+            }
+            out.push(']');
+            return Ok(());
+        }
+        BoltType::Map(m) => {
+            out.push('{');
+            for (i, (key, value)) in m.value.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_identifier(out, &key.value);
+                out.push_str(": ");
+                write_literal(out, value)?;
+            }
+            out.push('}');
+            return Ok(());
+        }
+        BoltType::Point2D(p) => {
+            write_point(out, p.sr_id.value, &[p.x.value, p.y.value]).unwrap();
+            return Ok(());
+        }
+        BoltType::Point3D(p) => {
+            write_point(out, p.sr_id.value, &[p.x.value, p.y.value, p.z.value]).unwrap();
+            return Ok(());
+        }
+        BoltType::DateTimeZoneIdUtc(d) => {
+            // the local time isn't known without a time zone database, let the server work it out
+            let (datetime, zone_id): (DateTime<Utc>, String) = d.clone().try_into()?;
+            write!(
+                out,
+                "datetime({{epochSeconds: {}, nanosecond: {}, timezone: ",
+                datetime.timestamp(),
+                datetime.timestamp_subsec_nanos()
+            )
+            .unwrap();
+            write_quoted(out, &zone_id, '\'').unwrap();
+            out.push_str("})");
+            return Ok(());
+        }
+        BoltType::Duration(_) => "duration",
+        BoltType::Date(_) => "date",
+        BoltType::Time(_) => "time",
+        BoltType::LocalTime(_) => "localtime",
+        BoltType::DateTime(_) | BoltType::DateTimeZoneId(_) => "datetime",
+        BoltType::LocalDateTime(_) => "localdatetime",
+        BoltType::Bytes(_)
+        | BoltType::Node(_)
+        | BoltType::Relation(_)
+        | BoltType::UnboundedRelation(_)
+        | BoltType::Path(_) => return Err(Error::ConverstionError),
+    };
+    write!(out, "{}('{}')", function, iso_8601(value)?).unwrap();
+    Ok(())
+}
+
+fn write_float_literal(out: &mut dyn Write, value: f64) -> fmt::Result {
+    match value {
+        x if x.is_nan() => out.write_str("0.0 / 0.0"),
+        x if x.is_infinite() && x > 0.0 => out.write_str("1.0 / 0.0"),
+        x if x.is_infinite() => out.write_str("-1.0 / 0.0"),
+        x => write!(out, "{:?}", x),
+    }
+}
+
+fn write_quoted(out: &mut dyn Write, value: &str, quote: char) -> fmt::Result {
+    out.write_char(quote)?;
+    for c in value.chars() {
+        match c {
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            '\u{8}' => out.write_str("\\b")?,
+            '\u{c}' => out.write_str("\\f")?,
+            c if c == quote => write!(out, "\\{}", c)?,
+            c if c.is_control() => write!(out, "\\u{:04X}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char(quote)
+}
+
+/// Quotes a label, relationship type, property key or variable in backticks when it isn't a plain
+/// identifier, so that it can be written in a query, e.g. `Person` stays as is while `Team Member`
+/// becomes `` `Team Member` `` and `` a`b `` becomes `` `a``b` ``.
+///
+/// Plain identifiers are ASCII letters, digits and underscores, not starting with a digit, which
+/// aren't reserved words such as `match` or `null`. Names can't be empty nor contain the null
+/// character, they fail with [`Error::InvalidIdentifier`].
+///
+/// ```
+/// use neo4jrs::escape_identifier;
+///
+/// assert_eq!(escape_identifier("Person").unwrap(), "Person");
+/// assert_eq!(escape_identifier("Person`) detach delete (n").unwrap(), "`Person``) detach delete (n`");
+/// assert_eq!(escape_identifier("return").unwrap(), "`return`");
+/// assert!(escape_identifier("").is_err());
+/// ```
+pub fn escape_identifier(identifier: &str) -> Result<String> {
+    if identifier.is_empty() || identifier.contains('\0') {
+        return Err(Error::InvalidIdentifier(identifier.to_owned()));
+    }
+    let mut escaped = String::with_capacity(identifier.len());
+    write_identifier(&mut escaped, identifier);
+    Ok(escaped)
+}
+
+/// Writes the identifier as is when it is a valid cypher identifier, quoted in backticks
+/// otherwise.
+pub(crate) fn write_identifier(out: &mut String, identifier: &str) {
+    let mut chars = identifier.chars();
+    let is_plain = chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !is_reserved(identifier);
+    if is_plain {
+        out.push_str(identifier);
+    } else {
+        out.push('`');
+        out.push_str(&identifier.replace('`', "``"));
+        out.push('`');
+    }
+}
+
+/// The reserved words of cypher, which aren't identifiers unless quoted
+const RESERVED_WORDS: &[&str] = &[
+    "add",
+    "all",
+    "and",
+    "as",
+    "asc",
+    "ascending",
+    "assert",
+    "by",
+    "call",
+    "case",
+    "constraint",
+    "contains",
+    "count",
+    "create",
+    "csv",
+    "delete",
+    "desc",
+    "descending",
+    "detach",
+    "distinct",
+    "do",
+    "drop",
+    "else",
+    "end",
+    "ends",
+    "exists",
+    "false",
+    "for",
+    "foreach",
+    "in",
+    "index",
+    "is",
+    "join",
+    "key",
+    "limit",
+    "load",
+    "mandatory",
+    "match",
+    "merge",
+    "node",
+    "not",
+    "null",
+    "of",
+    "on",
+    "optional",
+    "or",
+    "order",
+    "remove",
+    "require",
+    "return",
+    "scalar",
+    "scan",
+    "set",
+    "skip",
+    "start",
+    "starts",
+    "then",
+    "true",
+    "union",
+    "unique",
+    "unwind",
+    "use",
+    "using",
+    "when",
+    "where",
+    "with",
+    "xor",
+    "yield",
+];
+
+fn is_reserved(identifier: &str) -> bool {
+    RESERVED_WORDS
+        .iter()
+        .any(|word| word.eq_ignore_ascii_case(identifier))
+}
+
+/// Renders a temporal value as an ISO-8601 string, a datetime with a zone id is rendered as
+/// `2015-07-01T08:59:60.123[Europe/Paris]`.
+pub(crate) fn iso_8601(value: &BoltType) -> Result<String> {
+    let iso = match value.clone() {
+        BoltType::Duration(d) => Duration::from(d).to_string(),
+        BoltType::Date(d) => {
+            let date: NaiveDate = d.try_into()?;
+            date.format("%Y-%m-%d").to_string()
+        }
+        BoltType::Time(t) => {
+            let (time, offset): (NaiveTime, FixedOffset) = t.into();
+            format!("{}{}", format_time(time), format_offset(offset))
+        }
+        BoltType::LocalTime(t) => {
+            let time: NaiveTime = t.into();
+            format_time(time)
+        }
+        BoltType::DateTime(d) => {
+            let datetime: DateTime<FixedOffset> = d.try_into()?;
+            datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        }
+        BoltType::LocalDateTime(d) => {
+            let datetime: NaiveDateTime = d.try_into()?;
+            format_datetime(datetime)
+        }
+        BoltType::DateTimeZoneId(d) => {
+            let (datetime, zone_id): (NaiveDateTime, String) = d.try_into()?;
+            format!("{}[{}]", format_datetime(datetime), zone_id)
+        }
+        BoltType::DateTimeZoneIdUtc(d) => {
+            let (datetime, zone_id): (DateTime<Utc>, String) = d.try_into()?;
+            let datetime = datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+            format!("{}[{}]", datetime, zone_id)
+        }
+        _ => return Err(Error::ConverstionError),
+    };
+    Ok(iso)
+}
+
+fn format_time(time: NaiveTime) -> String {
+    time.format("%H:%M:%S%.f").to_string()
+}
+
+fn format_datetime(datetime: NaiveDateTime) -> String {
+    datetime.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+}
+
+fn format_offset(offset: FixedOffset) -> String {
+    if offset.local_minus_utc() == 0 {
+        "Z".to_owned()
+    } else {
+        offset.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn bolt<T: Into<BoltType>>(value: T) -> BoltType {
+        value.into()
+    }
+
+    fn node(labels: Vec<&str>, name: &str) -> BoltNode {
+        BoltNode::new(
+            1.into(),
+            labels
+                .into_iter()
+                .map(|l| l.into())
+                .collect::<Vec<BoltType>>()
+                .into(),
+            vec![("name".into(), name.into())].into_iter().collect(),
+        )
+    }
+
+    #[test]
+    fn should_display_scalars() {
+        assert_eq!(BoltType::Null(BoltNull).to_string(), "null");
+        assert_eq!(bolt(true).to_string(), "true");
+        assert_eq!(bolt(-42).to_string(), "-42");
+        assert_eq!(bolt(1.0).to_string(), "1.0");
+        assert_eq!(bolt(f64::NAN).to_string(), "NaN");
+        assert_eq!(bolt(f64::NEG_INFINITY).to_string(), "-Infinity");
+        assert_eq!(bolt("plain").to_string(), "plain");
+        assert_eq!(bolt(vec![1u8, 255]).to_string(), "0x01ff");
+    }
+
+    #[test]
+    fn should_display_collections() {
+        let list = bolt(vec![bolt(1), bolt("a\"b"), bolt(vec![bolt(2.5)])]);
+        assert_eq!(list.to_string(), r#"[1, "a\"b", [2.5]]"#);
+
+        let map: HashMap<&str, BoltType> = vec![("name", bolt("Mark"))].into_iter().collect();
+        assert_eq!(bolt(map).to_string(), r#"{name: "Mark"}"#);
+    }
+
+    #[test]
+    fn should_display_graph_entities() {
+        let mark = node(vec!["Person", "Admin"], "Mark");
+        let james = node(vec!["Person"], "James");
+        assert_eq!(
+            BoltType::Node(mark.clone()).to_string(),
+            r#"(:Person:Admin {name: "Mark"})"#
+        );
+
+        let knows = BoltUnboundedRelation::new(9.into(), "KNOWS".into(), BoltMap::default());
+        let path = BoltPath {
+            nodes: vec![BoltType::Node(mark), BoltType::Node(james)].into(),
+            rels: vec![BoltType::UnboundedRelation(knows)].into(),
+            ids: vec![bolt(-1), bolt(1)].into(),
+        };
+        assert_eq!(
+            BoltType::Path(path).to_string(),
+            r#"(:Person:Admin {name: "Mark"})<-[:KNOWS]-(:Person {name: "James"})"#
+        );
+    }
+
+    #[test]
+    fn should_display_temporal_and_spatial_values() {
+        let date = NaiveDate::from_ymd_opt(2010, 1, 1).unwrap();
+        let time = NaiveTime::from_hms_nano_opt(7, 8, 9, 100).unwrap();
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+
+        assert_eq!(bolt(date).to_string(), "2010-01-01");
+        assert_eq!(bolt((time, offset)).to_string(), "07:08:09.000000100+02:00");
+        assert_eq!(
+            bolt(date.and_time(time)).to_string(),
+            "2010-01-01T07:08:09.000000100"
+        );
+        assert_eq!(
+            bolt(Duration::new(14, 3, 3_723, 500_000_000)).to_string(),
+            "P1Y2M3DT1H2M3.5S"
+        );
+        assert_eq!(
+            bolt(crate::Point2D::cartesian(1.0, 2.5)).to_string(),
+            "point({x: 1.0, y: 2.5, crs: 'cartesian'})"
+        );
+    }
+
+    #[test]
+    fn should_render_cypher_literals() {
+        let literal = |value: BoltType| value.to_cypher_literal().unwrap();
+
+        assert_eq!(literal(bolt("it's a \\ \n")), r"'it\'s a \\ \n'");
+        assert_eq!(literal(bolt(f64::INFINITY)), "1.0 / 0.0");
+        assert_eq!(
+            literal(bolt(vec![bolt(1), BoltType::Null(BoltNull)])),
+            "[1, null]"
+        );
+
+        let map: HashMap<&str, BoltType> = vec![("first name", bolt(true))].into_iter().collect();
+        assert_eq!(literal(bolt(map)), "{`first name`: true}");
+        let map: HashMap<&str, BoltType> = vec![("a`b", bolt(1))].into_iter().collect();
+        assert_eq!(literal(bolt(map)), "{`a``b`: 1}");
+
+        let date = NaiveDate::from_ymd_opt(2010, 1, 1).unwrap();
+        assert_eq!(literal(bolt(date)), "date('2010-01-01')");
+        assert_eq!(
+            literal(bolt((date.and_hms_opt(1, 2, 3).unwrap(), "Europe/Paris"))),
+            "datetime('2010-01-01T01:02:03[Europe/Paris]')"
+        );
+        assert_eq!(literal(bolt(Duration::new(0, 1, 0, 0))), "duration('P1D')");
+        assert_eq!(
+            literal(bolt(crate::Point3D::wgs84(12.5, 55.7, 10.0))),
+            "point({x: 12.5, y: 55.7, z: 10.0, crs: 'wgs-84-3d'})"
+        );
+    }
+
+    #[test]
+    fn should_refuse_values_without_literal() {
+        assert!(bolt(vec![1u8]).to_cypher_literal().is_err());
+        assert!(BoltType::Node(node(vec![], "Mark"))
+            .to_cypher_literal()
+            .is_err());
+    }
+
+    #[test]
+    fn should_quote_identifiers_when_needed() {
+        let escape = |name| escape_identifier(name).unwrap();
+
+        assert_eq!(escape("Person"), "Person");
+        assert_eq!(escape("_private_2"), "_private_2");
+        assert_eq!(escape("Team Member"), "`Team Member`");
+        assert_eq!(escape("2fa"), "`2fa`");
+        assert_eq!(escape("Café"), "`Café`");
+        assert_eq!(escape("人物"), "`人物`");
+        assert_eq!(escape("a`b"), "`a``b`");
+        assert_eq!(escape("`"), "````");
+        assert_eq!(escape("match"), "`match`");
+        assert_eq!(escape("NULL"), "`NULL`");
+        assert_eq!(escape("Return"), "`Return`");
+        assert_eq!(escape("add"), "`add`");
+        assert_eq!(escape("USING"), "`USING`");
+        assert_eq!(escape("matches"), "matches");
+        assert!(matches!(
+            escape_identifier(""),
+            Err(Error::InvalidIdentifier(_))
+        ));
+        assert!(matches!(
+            escape_identifier("a\0"),
+            Err(Error::InvalidIdentifier(_))
+        ));
+    }
+}
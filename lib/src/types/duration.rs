@@ -1,7 +1,7 @@
 use crate::types::*;
 use neo4jrs_macros::BoltStruct;
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB4, 0x45)]
 pub struct BoltDuration {
     months: BoltInteger,
@@ -24,6 +24,22 @@ impl BoltDuration {
             nanoseconds,
         }
     }
+
+    pub fn months(&self) -> i64 {
+        self.months.value
+    }
+
+    pub fn days(&self) -> i64 {
+        self.days.value
+    }
+
+    pub fn seconds(&self) -> i64 {
+        self.seconds.value
+    }
+
+    pub fn nanoseconds(&self) -> i64 {
+        self.nanoseconds.value
+    }
 }
 
 impl Into<BoltDuration> for std::time::Duration {
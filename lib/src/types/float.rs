@@ -2,12 +2,13 @@ use crate::errors::*;
 use crate::version::Version;
 use bytes::*;
 use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
 use std::mem;
 use std::rc::Rc;
 
 pub const MARKER: u8 = 0xC1;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct BoltFloat {
     pub value: f64,
 }
@@ -22,6 +23,27 @@ impl BoltFloat {
     }
 }
 
+/// Unlike `f64`, all NaNs are equal to each other so that floats can be compared and hashed,
+/// `0.0` and `-0.0` are still equal.
+impl PartialEq for BoltFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value || (self.value.is_nan() && other.value.is_nan())
+    }
+}
+
+impl Eq for BoltFloat {}
+
+impl Hash for BoltFloat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let value = match self.value {
+            v if v.is_nan() => f64::NAN,
+            0.0 => 0.0,
+            v => v,
+        };
+        value.to_bits().hash(state)
+    }
+}
+
 impl BoltFloat {
     pub fn parse(_: Version, input: Rc<RefCell<Bytes>>) -> Result<BoltFloat> {
         let mut input = input.borrow_mut();
@@ -11,7 +11,7 @@ pub const INT_16: u8 = 0xC9;
 pub const INT_32: u8 = 0xCA;
 pub const INT_64: u8 = 0xCB;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct BoltInteger {
     pub value: i64,
 }
@@ -11,7 +11,7 @@ pub const SMALL: u8 = 0xD4;
 pub const MEDIUM: u8 = 0xD5;
 pub const LARGE: u8 = 0xD6;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct BoltList {
     pub value: Vec<BoltType>,
 }
@@ -37,6 +37,10 @@ impl BoltList {
         self.value.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
     pub fn push(&mut self, value: BoltType) {
         self.value.push(value);
     }
@@ -82,6 +86,12 @@ impl From<Vec<BoltType>> for BoltList {
     }
 }
 
+impl Into<BoltType> for BoltList {
+    fn into(self) -> BoltType {
+        BoltType::List(self)
+    }
+}
+
 impl BoltList {
     pub fn into_bytes(self, version: Version) -> Result<Bytes> {
         let mut values = BytesMut::new();
@@ -2,36 +2,59 @@ use crate::errors::*;
 use crate::types::*;
 use crate::version::Version;
 use bytes::*;
+use indexmap::IndexMap;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
 use std::convert::TryInto;
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 
 use std::mem;
 use std::rc::Rc;
 
+pub use indexmap::map::{Entry, IntoIter, Iter, Keys, Values};
+
 pub const TINY: u8 = 0xA0;
 pub const SMALL: u8 = 0xD8;
 pub const MEDIUM: u8 = 0xD9;
 pub const LARGE: u8 = 0xDA;
 
-#[derive(Debug, PartialEq, Clone)]
+/// A map keeping its entries in insertion order, which is also the order they are sent and
+/// received in. Two maps with the same entries in a different order are still equal.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BoltMap {
-    pub value: HashMap<BoltString, BoltType>,
+    pub value: IndexMap<BoltString, BoltType>,
 }
 
 impl Default for BoltMap {
     fn default() -> Self {
         BoltMap {
-            value: HashMap::new(),
+            value: IndexMap::new(),
         }
     }
 }
 
+/// Entries are hashed independently of their order, to be consistent with equality
+impl Hash for BoltMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let entries = self
+            .value
+            .iter()
+            .map(|entry| {
+                let mut hasher = DefaultHasher::new();
+                entry.hash(&mut hasher);
+                hasher.finish()
+            })
+            .fold(0u64, u64::wrapping_add);
+        state.write_usize(self.value.len());
+        state.write_u64(entries);
+    }
+}
+
 impl BoltMap {
     pub fn with_capacity(capacity: usize) -> Self {
         BoltMap {
-            value: HashMap::with_capacity(capacity),
+            value: IndexMap::with_capacity(capacity),
         }
     }
 
@@ -39,10 +62,41 @@ impl BoltMap {
         self.value.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Inserts the entry, a key already present keeps its position
     pub fn put(&mut self, key: BoltString, value: BoltType) {
         self.value.insert(key, value);
     }
 
+    /// Removes the entry, preserving the order of the remaining ones
+    pub fn remove(&mut self, key: &str) -> Option<BoltType> {
+        self.value.shift_remove(&BoltString::new(key))
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.value.contains_key(&BoltString::new(key))
+    }
+
+    pub fn entry(&mut self, key: BoltString) -> Entry<'_, BoltString, BoltType> {
+        self.value.entry(key)
+    }
+
+    /// Entries in insertion order
+    pub fn iter(&self) -> Iter<'_, BoltString, BoltType> {
+        self.value.iter()
+    }
+
+    pub fn keys(&self) -> Keys<'_, BoltString, BoltType> {
+        self.value.keys()
+    }
+
+    pub fn values(&self) -> Values<'_, BoltString, BoltType> {
+        self.value.values()
+    }
+
     pub fn get<T: std::convert::TryFrom<BoltType>>(&self, key: &str) -> Option<T> {
         match self.value.get(&BoltString::new(key)) {
             Some(bolt_type) => {
@@ -78,6 +132,30 @@ impl FromIterator<(BoltString, BoltType)> for BoltMap {
     }
 }
 
+impl IntoIterator for BoltMap {
+    type Item = (BoltString, BoltType);
+    type IntoIter = IntoIter<BoltString, BoltType>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.value.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a BoltMap {
+    type Item = (&'a BoltString, &'a BoltType);
+    type IntoIter = Iter<'a, BoltString, BoltType>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.value.iter()
+    }
+}
+
+impl Into<BoltType> for BoltMap {
+    fn into(self) -> BoltType {
+        BoltType::Map(self)
+    }
+}
+
 impl BoltMap {
     pub fn into_bytes(self, version: Version) -> Result<Bytes> {
         let mut key_value_bytes = BytesMut::new();
@@ -215,4 +293,67 @@ mod tests {
         let deserialized_map: BoltMap = BoltMap::parse(Version::V4_1, bytes).unwrap();
         assert_eq!(map, deserialized_map);
     }
+
+    #[test]
+    fn should_preserve_insertion_order_through_serialization() {
+        let mut map = BoltMap::default();
+        for key in &["zeta", "alpha", "mu", "beta"] {
+            map.put((*key).into(), (*key).into());
+        }
+
+        let bytes = Rc::new(RefCell::new(map.into_bytes(Version::V4_1).unwrap()));
+        let map = BoltMap::parse(Version::V4_1, bytes).unwrap();
+
+        let keys: Vec<&str> = map.keys().map(|k| k.value.as_str()).collect();
+        assert_eq!(keys, vec!["zeta", "alpha", "mu", "beta"]);
+    }
+
+    #[test]
+    fn should_remove_entries_preserving_order() {
+        let mut map: BoltMap = vec![("c", 1), ("a", 2), ("b", 3)]
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+
+        assert!(map.contains_key("a"));
+        assert_eq!(map.remove("a"), Some(2.into()));
+        assert_eq!(map.remove("a"), None);
+        assert!(!map.contains_key("a"));
+
+        let entries: Vec<(String, BoltType)> = map.into_iter().map(|(k, v)| (k.value, v)).collect();
+        assert_eq!(
+            entries,
+            vec![("c".to_owned(), 1.into()), ("b".to_owned(), 3.into())]
+        );
+    }
+
+    #[test]
+    fn should_update_entries_in_place() {
+        let mut map = BoltMap::default();
+        map.put("a".into(), 1.into());
+        map.put("b".into(), 2.into());
+
+        map.entry("a".into()).or_insert_with(|| 0.into());
+        map.entry("c".into()).or_insert_with(|| 3.into());
+        if let BoltType::Integer(i) = map.entry("b".into()).or_insert_with(|| 0.into()) {
+            i.value += 10;
+        }
+        map.put("a".into(), 5.into());
+
+        let values: Vec<&BoltType> = map.values().collect();
+        assert_eq!(values, vec![&5.into(), &12.into(), &3.into()]);
+    }
+
+    #[test]
+    fn should_compare_maps_regardless_of_order() {
+        let mut a = BoltMap::default();
+        a.put("x".into(), 1.into());
+        a.put("y".into(), 2.into());
+        let mut b = BoltMap::default();
+        b.put("y".into(), 2.into());
+        b.put("x".into(), 1.into());
+
+        assert_eq!(a, b);
+        assert_ne!(a.iter().collect::<Vec<_>>(), b.iter().collect::<Vec<_>>());
+    }
 }
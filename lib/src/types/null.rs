@@ -1,7 +1,7 @@
 use bytes::*;
 use neo4jrs_macros::BoltStruct;
 
-#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xC0)]
 pub struct BoltNull;
 
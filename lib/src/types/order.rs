@@ -0,0 +1,385 @@
+//! A total order over bolt values, following the order used by `ORDER BY` in cypher.
+//!
+//! Values of different types are ordered as maps, nodes, relationships, lists, bytes, paths,
+//! points, datetimes, local datetimes, dates, times, local times, durations, strings, booleans,
+//! numbers and finally null.
+//!
+//! Within a type values are ordered the way cypher orders them: numbers by value whether they are
+//! integers or floats (with NaN after every other number), entities by id, lists element by
+//! element, maps by size then keys then values, zoned datetimes and times by instant, durations
+//! by their approximate length. Values cypher considers equal but that aren't equal, such as `1`
+//! and `1.0`, are then ordered by their content so that the order is consistent with `Eq`.
+//!
+//! A datetime with a zone id, sent with the bolt 4 encoding, only knows its local time. It is
+//! ordered by that local time as if it were UTC.
+
+use crate::types::*;
+use std::cmp::Ordering;
+
+/// Average length of a month in seconds, as used by neo4j to compare durations
+const AVERAGE_SECONDS_PER_MONTH: i128 = 2_629_746;
+
+impl PartialOrd for BoltType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BoltType {
+    fn cmp(&self, other: &Self) -> Ordering {
+        rank(self)
+            .cmp(&rank(other))
+            .then_with(|| compare_same_rank(self, other))
+    }
+}
+
+impl PartialOrd for BoltList {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BoltList {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl PartialOrd for BoltMap {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BoltMap {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (left, right) = (sorted_entries(self), sorted_entries(other));
+        left.len()
+            .cmp(&right.len())
+            .then_with(|| {
+                let keys = left.iter().map(|(k, _)| &k.value);
+                keys.cmp(right.iter().map(|(k, _)| &k.value))
+            })
+            .then_with(|| {
+                let values = left.iter().map(|(_, v)| v);
+                values.cmp(right.iter().map(|(_, v)| v))
+            })
+    }
+}
+
+fn sorted_entries(map: &BoltMap) -> Vec<(&BoltString, &BoltType)> {
+    let mut entries: Vec<_> = map.value.iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.value.cmp(&b.value));
+    entries
+}
+
+fn rank(value: &BoltType) -> u8 {
+    match value {
+        BoltType::Map(_) => 0,
+        BoltType::Node(_) => 1,
+        BoltType::Relation(_) | BoltType::UnboundedRelation(_) => 2,
+        BoltType::List(_) => 3,
+        BoltType::Bytes(_) => 4,
+        BoltType::Path(_) => 5,
+        BoltType::Point2D(_) | BoltType::Point3D(_) => 6,
+        BoltType::DateTime(_) | BoltType::DateTimeZoneId(_) | BoltType::DateTimeZoneIdUtc(_) => 7,
+        BoltType::LocalDateTime(_) => 8,
+        BoltType::Date(_) => 9,
+        BoltType::Time(_) => 10,
+        BoltType::LocalTime(_) => 11,
+        BoltType::Duration(_) => 12,
+        BoltType::String(_) => 13,
+        BoltType::Boolean(_) => 14,
+        BoltType::Integer(_) | BoltType::Float(_) => 15,
+        BoltType::Null(_) => 16,
+    }
+}
+
+/// Orders variants sharing a rank when their values compare equal, e.g. `1` before `1.0`
+fn variant(value: &BoltType) -> u8 {
+    match value {
+        BoltType::UnboundedRelation(_)
+        | BoltType::Point3D(_)
+        | BoltType::DateTimeZoneId(_)
+        | BoltType::Float(_) => 1,
+        BoltType::DateTimeZoneIdUtc(_) => 2,
+        _ => 0,
+    }
+}
+
+fn compare_same_rank(left: &BoltType, right: &BoltType) -> Ordering {
+    use BoltType::*;
+
+    let ordering = match (left, right) {
+        (Map(a), Map(b)) => a.cmp(b),
+        (Node(a), Node(b)) => {
+            a.id.value
+                .cmp(&b.id.value)
+                .then_with(|| a.labels.cmp(&b.labels))
+                .then_with(|| a.properties.cmp(&b.properties))
+        }
+        (Relation(a), Relation(b)) => {
+            a.id.value
+                .cmp(&b.id.value)
+                .then_with(|| a.start_node_id.value.cmp(&b.start_node_id.value))
+                .then_with(|| a.end_node_id.value.cmp(&b.end_node_id.value))
+                .then_with(|| a.typ.value.cmp(&b.typ.value))
+                .then_with(|| a.properties.cmp(&b.properties))
+        }
+        (UnboundedRelation(a), UnboundedRelation(b)) => {
+            a.id.value
+                .cmp(&b.id.value)
+                .then_with(|| a.typ.value.cmp(&b.typ.value))
+                .then_with(|| a.properties.cmp(&b.properties))
+        }
+        (Relation(a), UnboundedRelation(b)) => a.id.value.cmp(&b.id.value),
+        (UnboundedRelation(a), Relation(b)) => a.id.value.cmp(&b.id.value),
+        (List(a), List(b)) => a.cmp(b),
+        (Bytes(a), Bytes(b)) => a.value.cmp(&b.value),
+        (Path(a), Path(b)) => a
+            .nodes
+            .cmp(&b.nodes)
+            .then_with(|| a.rels.cmp(&b.rels))
+            .then_with(|| a.ids.cmp(&b.ids)),
+        (Point2D(_), _) | (Point3D(_), _) => {
+            let (srid, coordinates) = point(left);
+            let (other_srid, other_coordinates) = point(right);
+            srid.cmp(&other_srid).then_with(|| {
+                coordinates
+                    .iter()
+                    .zip(&other_coordinates)
+                    .map(|(a, b)| compare_floats(*a, *b))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+        }
+        (DateTime(_), _) | (DateTimeZoneId(_), _) | (DateTimeZoneIdUtc(_), _) => {
+            instant(left).cmp(&instant(right))
+        }
+        (LocalDateTime(a), LocalDateTime(b)) => {
+            (a.seconds.value, a.nanoseconds.value).cmp(&(b.seconds.value, b.nanoseconds.value))
+        }
+        (Date(a), Date(b)) => a.days.value.cmp(&b.days.value),
+        (Time(a), Time(b)) => {
+            let utc = |t: &BoltTime| {
+                t.nanoseconds.value as i128 - t.tz_offset_seconds.value as i128 * 1_000_000_000
+            };
+            utc(a).cmp(&utc(b))
+        }
+        (LocalTime(a), LocalTime(b)) => a.nanoseconds.value.cmp(&b.nanoseconds.value),
+        (Duration(a), Duration(b)) => {
+            let length = |d: &BoltDuration| {
+                let seconds = d.months() as i128 * AVERAGE_SECONDS_PER_MONTH
+                    + d.days() as i128 * 86_400
+                    + d.seconds() as i128;
+                seconds * 1_000_000_000 + d.nanoseconds() as i128
+            };
+            length(a).cmp(&length(b))
+        }
+        (String(a), String(b)) => a.value.cmp(&b.value),
+        (Boolean(a), Boolean(b)) => a.value.cmp(&b.value),
+        (Integer(a), Integer(b)) => a.value.cmp(&b.value),
+        (Float(a), Float(b)) => compare_floats(a.value, b.value),
+        (Integer(a), Float(b)) => compare_integer_float(a.value, b.value),
+        (Float(a), Integer(b)) => compare_integer_float(b.value, a.value).reverse(),
+        (Null(_), Null(_)) => Ordering::Equal,
+        _ => Ordering::Equal,
+    };
+
+    ordering
+        .then_with(|| variant(left).cmp(&variant(right)))
+        .then_with(|| tie_break(left, right))
+}
+
+/// Orders values cypher considers equal by their fields, e.g. times at the same instant in
+/// different offsets.
+fn tie_break(left: &BoltType, right: &BoltType) -> Ordering {
+    use BoltType::*;
+
+    match (left, right) {
+        (DateTime(a), DateTime(b)) => a.tz_offset_seconds.value.cmp(&b.tz_offset_seconds.value),
+        (DateTimeZoneId(a), DateTimeZoneId(b)) => a.tz_id.value.cmp(&b.tz_id.value),
+        (DateTimeZoneIdUtc(a), DateTimeZoneIdUtc(b)) => a.tz_id.value.cmp(&b.tz_id.value),
+        (Time(a), Time(b)) => a.tz_offset_seconds.value.cmp(&b.tz_offset_seconds.value),
+        (Duration(a), Duration(b)) => (a.months(), a.days(), a.seconds(), a.nanoseconds()).cmp(&(
+            b.months(),
+            b.days(),
+            b.seconds(),
+            b.nanoseconds(),
+        )),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Total order over floats, `-0.0` and `0.0` are equal and NaN is greater than any number
+fn compare_floats(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// Compares the exact values, converting either side could lose precision
+fn compare_integer_float(integer: i64, float: f64) -> Ordering {
+    const TWO_POW_63: f64 = 9_223_372_036_854_775_808.0;
+
+    if float.is_nan() || float >= TWO_POW_63 {
+        return Ordering::Less;
+    }
+    if float < -TWO_POW_63 {
+        return Ordering::Greater;
+    }
+    let truncated = float.trunc();
+    integer
+        .cmp(&(truncated as i64))
+        .then_with(|| compare_floats(0.0, float - truncated))
+}
+
+fn point(value: &BoltType) -> (i64, Vec<f64>) {
+    match value {
+        BoltType::Point2D(p) => (p.sr_id.value, vec![p.x.value, p.y.value]),
+        BoltType::Point3D(p) => (p.sr_id.value, vec![p.x.value, p.y.value, p.z.value]),
+        _ => (0, vec![]),
+    }
+}
+
+/// Seconds since the epoch in UTC and nanoseconds of a zoned datetime
+fn instant(value: &BoltType) -> (i128, i64) {
+    match value {
+        BoltType::DateTime(d) => (
+            d.seconds.value as i128 - d.tz_offset_seconds.value as i128,
+            d.nanoseconds.value,
+        ),
+        BoltType::DateTimeZoneId(d) => (d.seconds.value as i128, d.nanoseconds.value),
+        BoltType::DateTimeZoneIdUtc(d) => (d.seconds.value as i128, d.nanoseconds.value),
+        _ => (0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{HashMap, HashSet};
+    use std::hash::{Hash, Hasher};
+
+    fn bolt<T: Into<BoltType>>(value: T) -> BoltType {
+        value.into()
+    }
+
+    fn hash(value: &BoltType) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn should_order_across_types_like_cypher() {
+        let date = chrono::NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let map: HashMap<&str, i64> = vec![("a", 1)].into_iter().collect();
+        let mut values = vec![
+            BoltType::Null(BoltNull),
+            bolt(1),
+            bolt(true),
+            bolt("a"),
+            bolt(crate::Duration::new(0, 1, 0, 0)),
+            bolt(date),
+            bolt(vec![bolt(1)]),
+            bolt(map),
+        ];
+        let expected: Vec<BoltType> = values.iter().rev().cloned().collect();
+
+        values.sort();
+
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn should_order_numbers_by_value() {
+        let mut values = vec![
+            bolt(f64::NAN),
+            bolt(2),
+            bolt(1.5),
+            bolt(f64::NEG_INFINITY),
+            bolt(1.0),
+            bolt(1),
+            bolt(i64::MAX),
+            bolt(9.3e18),
+        ];
+
+        values.sort();
+
+        assert_eq!(
+            values,
+            vec![
+                bolt(f64::NEG_INFINITY),
+                bolt(1),
+                bolt(1.0),
+                bolt(1.5),
+                bolt(2),
+                bolt(i64::MAX),
+                bolt(9.3e18),
+                bolt(f64::NAN),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_order_lists_and_maps() {
+        let list = |values: Vec<i64>| bolt(values);
+        assert!(list(vec![1, 2]) < list(vec![1, 3]));
+        assert!(list(vec![1, 2]) < list(vec![1, 2, 0]));
+        assert!(list(vec![2]) > list(vec![1, 5]));
+
+        let map =
+            |entries: Vec<(&str, i64)>| bolt(entries.into_iter().collect::<HashMap<&str, i64>>());
+        assert!(map(vec![("b", 1)]) < map(vec![("a", 1), ("b", 1)]));
+        assert!(map(vec![("a", 2)]) < map(vec![("b", 1)]));
+        assert!(map(vec![("a", 1)]) < map(vec![("a", 2)]));
+    }
+
+    #[test]
+    fn should_order_zoned_values_by_instant() {
+        use chrono::{DateTime, FixedOffset, NaiveTime};
+
+        let paris = bolt(DateTime::parse_from_rfc3339("2020-01-01T12:00:00+01:00").unwrap());
+        let utc = bolt(DateTime::parse_from_rfc3339("2020-01-01T11:30:00+00:00").unwrap());
+        assert!(paris < utc);
+
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let ahead = bolt((noon, FixedOffset::east_opt(3600).unwrap()));
+        let behind = bolt((noon, FixedOffset::east_opt(0).unwrap()));
+        assert!(ahead < behind);
+    }
+
+    #[test]
+    fn should_be_consistent_with_eq_and_hash() {
+        assert_eq!(bolt(f64::NAN), bolt(f64::NAN));
+        assert_eq!(hash(&bolt(f64::NAN)), hash(&bolt(-f64::NAN)));
+        assert_eq!(bolt(0.0), bolt(-0.0));
+        assert_eq!(hash(&bolt(0.0)), hash(&bolt(-0.0)));
+        assert_eq!(bolt(0.0).cmp(&bolt(-0.0)), Ordering::Equal);
+        assert_ne!(bolt(1), bolt(1.0));
+        assert_ne!(bolt(1).cmp(&bolt(1.0)), Ordering::Equal);
+
+        let map =
+            |entries: Vec<(&str, i64)>| bolt(entries.into_iter().collect::<HashMap<&str, i64>>());
+        let a = map(vec![("a", 1), ("b", 2), ("c", 3)]);
+        let b = map(vec![("c", 3), ("b", 2), ("a", 1)]);
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+
+        let distinct: HashSet<BoltType> = vec![
+            bolt(f64::NAN),
+            bolt(f64::NAN),
+            bolt(vec![a.clone()]),
+            bolt(vec![b]),
+            bolt("x"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(distinct.len(), 3);
+    }
+}
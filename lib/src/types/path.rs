@@ -1,7 +1,7 @@
 use crate::types::*;
 use neo4jrs_macros::BoltStruct;
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB3, 0x50)]
 pub struct BoltPath {
     pub nodes: BoltList,
@@ -1,7 +1,7 @@
 use crate::types::*;
 use neo4jrs_macros::BoltStruct;
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB3, 0x58)]
 pub struct BoltPoint2D {
     pub sr_id: BoltInteger,
@@ -9,7 +9,7 @@ pub struct BoltPoint2D {
     pub y: BoltFloat,
 }
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB4, 0x59)]
 pub struct BoltPoint3D {
     pub sr_id: BoltInteger,
@@ -1,7 +1,7 @@
 use crate::types::*;
 use neo4jrs_macros::BoltStruct;
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB5, 0x52)]
 pub struct BoltRelation {
     pub id: BoltInteger,
@@ -11,7 +11,7 @@ pub struct BoltRelation {
     pub properties: BoltMap,
 }
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB3, 0x72)]
 pub struct BoltUnboundedRelation {
     pub id: BoltInteger,
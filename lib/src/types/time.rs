@@ -1,18 +1,20 @@
+#[cfg(feature = "time")]
+use crate::errors::Error;
 use crate::types::*;
 use chrono::{FixedOffset, NaiveTime, Offset, Timelike};
 use neo4jrs_macros::BoltStruct;
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB2, 0x54)]
 pub struct BoltTime {
-    nanoseconds: BoltInteger,
-    tz_offset_seconds: BoltInteger,
+    pub(crate) nanoseconds: BoltInteger,
+    pub(crate) tz_offset_seconds: BoltInteger,
 }
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, BoltStruct)]
 #[signature(0xB1, 0x74)]
 pub struct BoltLocalTime {
-    nanoseconds: BoltInteger,
+    pub(crate) nanoseconds: BoltInteger,
 }
 
 impl Into<BoltTime> for (NaiveTime, FixedOffset) {
@@ -32,8 +34,9 @@ impl Into<(NaiveTime, FixedOffset)> for BoltTime {
         let seconds = (nanos / 1_000_000_000) as u32;
         let nanoseconds = (nanos % 1_000_000_000) as u32;
         (
-            NaiveTime::from_num_seconds_from_midnight(seconds, nanoseconds),
-            FixedOffset::east(self.tz_offset_seconds.value as i32),
+            NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanoseconds)
+                .expect("invalid time"),
+            FixedOffset::east_opt(self.tz_offset_seconds.value as i32).expect("invalid offset"),
         )
     }
 }
@@ -53,10 +56,70 @@ impl Into<NaiveTime> for BoltLocalTime {
         let nanos = self.nanoseconds.value;
         let seconds = (nanos / 1_000_000_000) as u32;
         let nanoseconds = (nanos % 1_000_000_000) as u32;
-        NaiveTime::from_num_seconds_from_midnight(seconds, nanoseconds)
+        NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanoseconds).expect("invalid time")
     }
 }
 
+#[cfg(feature = "time")]
+impl Into<BoltTime> for (::time::Time, ::time::UtcOffset) {
+    fn into(self) -> BoltTime {
+        BoltTime {
+            nanoseconds: nanoseconds_from_midnight(self.0).into(),
+            tz_offset_seconds: (self.1.whole_seconds() as i64).into(),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl BoltTime {
+    pub fn try_into_time(self) -> Result<(::time::Time, ::time::UtcOffset)> {
+        let time = time_from_nanoseconds(self.nanoseconds.value)?;
+        let offset = std::convert::TryFrom::try_from(self.tz_offset_seconds.value)
+            .ok()
+            .and_then(|seconds| ::time::UtcOffset::from_whole_seconds(seconds).ok())
+            .ok_or(Error::ConverstionError)?;
+        Ok((time, offset))
+    }
+}
+
+#[cfg(feature = "time")]
+impl Into<BoltLocalTime> for ::time::Time {
+    fn into(self) -> BoltLocalTime {
+        BoltLocalTime {
+            nanoseconds: nanoseconds_from_midnight(self).into(),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl BoltLocalTime {
+    pub fn try_into_time(self) -> Result<::time::Time> {
+        time_from_nanoseconds(self.nanoseconds.value)
+    }
+}
+
+#[cfg(feature = "time")]
+fn nanoseconds_from_midnight(time: ::time::Time) -> i64 {
+    let (hour, minute, second, nanosecond) = time.as_hms_nano();
+    let seconds = hour as i64 * 3600 + minute as i64 * 60 + second as i64;
+    seconds * 1_000_000_000 + nanosecond as i64
+}
+
+#[cfg(feature = "time")]
+fn time_from_nanoseconds(nanos: i64) -> Result<::time::Time> {
+    if !(0..86_400 * 1_000_000_000).contains(&nanos) {
+        return Err(Error::ConverstionError);
+    }
+    let seconds = nanos / 1_000_000_000;
+    ::time::Time::from_hms_nano(
+        (seconds / 3600) as u8,
+        (seconds / 60 % 60) as u8,
+        (seconds % 60) as u8,
+        (nanos % 1_000_000_000) as u32,
+    )
+    .map_err(|_| Error::ConverstionError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,7 +131,7 @@ mod tests {
     #[test]
     fn should_serialize_time() {
         let time = NaiveTime::from_hms_nano_opt(7, 8, 9, 100).unwrap();
-        let offset = FixedOffset::east(2 * 3600);
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
 
         let time: BoltTime = (time, offset).into();
 
@@ -92,7 +155,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(time.to_string(), "07:08:09.000000100");
-        assert_eq!(offset, FixedOffset::east(2 * 3600));
+        assert_eq!(offset, FixedOffset::east_opt(2 * 3600).unwrap());
     }
 
     #[test]
@@ -122,4 +185,63 @@ mod tests {
 
         assert_eq!(time.to_string(), "07:08:09.000000100");
     }
+
+    #[cfg(feature = "time")]
+    mod time_crate {
+        use super::*;
+        use ::time::{Time, UtcOffset};
+
+        #[test]
+        fn should_convert_time_with_offset() {
+            let time = Time::from_hms_nano(7, 8, 9, 100).unwrap();
+            let offset = UtcOffset::from_hms(2, 0, 0).unwrap();
+
+            let bolt: BoltTime = (time, offset).into();
+
+            assert_eq!(
+                bolt.clone().into_bytes(Version::V4_1).unwrap(),
+                Bytes::from_static(&[
+                    0xB2, 0x54, 0xCB, 0x00, 0x00, 0x17, 0x5D, 0x2F, 0xB8, 0x3A, 0x64, 0xC9, 0x1C,
+                    0x20,
+                ])
+            );
+            let converted = bolt.try_into_time().unwrap();
+            assert_eq!(converted, (time, offset));
+        }
+
+        #[test]
+        fn should_round_trip_local_time_at_nanosecond_boundaries() {
+            for time in &[
+                Time::MIDNIGHT,
+                Time::from_hms_nano(0, 0, 0, 1).unwrap(),
+                Time::from_hms_nano(12, 0, 0, 999_999_999).unwrap(),
+                Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap(),
+            ] {
+                let bolt: BoltLocalTime = (*time).into();
+                let converted = bolt.try_into_time().unwrap();
+                assert_eq!(&converted, time);
+            }
+
+            let last: BoltLocalTime = Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap().into();
+            assert_eq!(last.nanoseconds.value, 86_400 * 1_000_000_000 - 1);
+        }
+
+        #[test]
+        fn should_reject_times_out_of_range() {
+            for nanoseconds in &[-1i64, 86_400 * 1_000_000_000] {
+                let bolt = BoltLocalTime {
+                    nanoseconds: (*nanoseconds).into(),
+                };
+                let converted = bolt.try_into_time();
+                assert!(converted.is_err());
+            }
+
+            let bolt = BoltTime {
+                nanoseconds: 0.into(),
+                tz_offset_seconds: (26 * 3600).into(),
+            };
+            let converted = bolt.try_into_time();
+            assert!(converted.is_err());
+        }
+    }
 }
@@ -0,0 +1,457 @@
+//! Compiles the derives and `cypher!` in a crate importing `neo4jrs::*`, whose `Result` and
+//! `Error` shadow those of the prelude.
+use neo4jrs::types::{BoltList, BoltMap, BoltNode, BoltType};
+use neo4jrs::*;
+use std::convert::TryFrom;
+
+#[derive(Value, Debug, Clone, PartialEq)]
+#[neo4j(rename_all = "lowercase")]
+enum Status {
+    Active,
+    Suspended,
+}
+
+#[derive(Value, Debug, Clone, PartialEq)]
+struct Email(String);
+
+#[derive(Properties, Debug, Clone, PartialEq)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[derive(Label, Debug, Clone, PartialEq)]
+struct Company {
+    id: i64,
+    name: String,
+}
+
+#[derive(Label, Debug, Clone, PartialEq)]
+#[neo4j(rename_all = "camelCase", detach_delete)]
+struct Person {
+    id: i64,
+    #[neo4j(unique)]
+    first_name: String,
+    age: Option<i64>,
+    status: Status,
+    email: Email,
+    #[neo4j(flatten)]
+    address: Address,
+    #[neo4j(relation = "WORKS_AT")]
+    employer: Option<Company>,
+    #[neo4j(relation = "MANAGES", direction = "in")]
+    managers: Vec<Person>,
+}
+
+#[derive(Label, Debug, Clone, PartialEq)]
+#[neo4j(soft_delete)]
+struct Document {
+    id: i64,
+    body: String,
+    #[neo4j(version)]
+    version: i64,
+    #[neo4j(created_at)]
+    created_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    #[neo4j(updated_at)]
+    updated_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    #[neo4j(relation = "CITES")]
+    cited: Vec<Document>,
+}
+
+#[derive(Label, Debug, Clone, PartialEq)]
+#[neo4j(identifier = ["org", "email"], chunk_size = 250)]
+struct User {
+    org: String,
+    #[neo4j(rename = "e-mail")]
+    email: String,
+    #[neo4j(on_create)]
+    joined_at: i64,
+    #[neo4j(on_match)]
+    seen_at: i64,
+    #[neo4j(serialize_with = "lowercase")]
+    nickname: Option<String>,
+    #[neo4j(skip)]
+    cache: Vec<String>,
+    #[neo4j(skip, default = "default_score")]
+    score: i64,
+}
+
+fn lowercase(value: &str) -> BoltType {
+    value.to_lowercase().into()
+}
+
+fn default_score() -> i64 {
+    10
+}
+
+#[derive(Label, Debug, Clone, PartialEq)]
+#[neo4j(identifier = "0")]
+struct Code(String, #[neo4j(rename = "label")] Option<String>);
+
+#[derive(Label)]
+struct PersonView<'a> {
+    id: i64,
+    name: &'a str,
+}
+
+#[derive(Label, Debug, Clone, PartialEq)]
+struct Tagged<T: Send>
+where
+    T: Sync,
+{
+    id: i64,
+    payload: Option<T>,
+}
+
+#[derive(Value, Debug, Clone, PartialEq)]
+struct Options(String);
+
+#[derive(Label, Debug, Clone, PartialEq)]
+struct Survey {
+    id: i64,
+    #[neo4j(with = "answers")]
+    answers: Vec<Option<i64>>,
+    options: Options,
+}
+
+/// Sends the unanswered questions as `-1`
+mod answers {
+    use neo4jrs::types::BoltType;
+    use std::convert::TryFrom;
+
+    pub fn serialize(answers: &[Option<i64>]) -> BoltType {
+        let answers: Vec<i64> = answers.iter().map(|a| a.unwrap_or(-1)).collect();
+        answers.into()
+    }
+
+    pub fn deserialize(value: BoltType) -> neo4jrs::Result<Vec<Option<i64>>> {
+        let answers = Vec::<i64>::try_from(value)?;
+        Ok(answers
+            .into_iter()
+            .map(|a| Some(a).filter(|a| *a >= 0))
+            .collect())
+    }
+}
+
+#[derive(Relationship, Debug, Clone, PartialEq)]
+#[neo4j(type = "WORKS_AT", from = Person, to = Company)]
+struct WorksAt {
+    since: i64,
+}
+
+/// Only compiled, every generated method is called with the types they take and return
+#[allow(dead_code)]
+async fn generated_methods(graph: &Graph) -> Result<()> {
+    let company = Company {
+        id: 1,
+        name: "Acme".to_owned(),
+    };
+    company.persist(graph).await?;
+    let mut alice = Person::find_one_by_id(1, graph).await?;
+    alice.employer = Some(company.clone());
+    alice.persist(graph).await?;
+    alice.upsert(graph).await?;
+    let txn = graph.start_txn().await?;
+    alice.persist(&txn).await?;
+    txn.commit().await?;
+    alice.update(graph).await?;
+    alice.load_employer(graph).await?;
+    let people: Vec<Person> = Person::find()
+        .where_age_gte(18)
+        .where_first_name_starts_with("A")
+        .order_by_first_name()
+        .include_employer()
+        .skip(1)
+        .limit(10)
+        .all(graph)
+        .await?;
+    let _: usize = Person::insert_many(&people, graph).await?;
+    let _: usize = Person::upsert_many(&people, graph).await?;
+    let _: usize = Person::delete_where_first_name("Alice", graph).await?;
+    let _: usize = alice.delete(graph).await?;
+    Person::ensure_schema(graph).await?;
+
+    let mut document = Document::find_one_by_id(1, graph).await?;
+    document.update(graph).await?;
+    let _: usize = document.delete(graph).await?;
+
+    let user = User::find()
+        .where_nickname("bob")
+        .first(graph)
+        .await?
+        .unwrap();
+    user.persist(graph).await?;
+    let _: User = user.upsert(graph).await?;
+    let _: usize = User::upsert_many(&[user], graph).await?;
+
+    let code: Option<Code> = Code::find().where_1_is_null().first(graph).await?;
+    code.unwrap().insert(graph).await?;
+
+    let view = PersonView {
+        id: 1,
+        name: "Alice",
+    };
+    view.insert(graph).await?;
+    view.update(graph).await?;
+    let _: usize = PersonView::insert_many(&[view], graph).await?;
+
+    let tagged: Option<Tagged<String>> = Tagged::find()
+        .where_payload("urgent")
+        .where_payload_in(vec!["urgent", "later"])
+        .first(graph)
+        .await?;
+    if let Some(tagged) = tagged {
+        tagged.persist(graph).await?;
+    }
+
+    let survey = Survey::find_one_by_id(1, graph).await?;
+    survey.persist(graph).await?;
+
+    let works_at = WorksAt { since: 2021 };
+    works_at.connect(&alice, &company, graph).await?;
+    works_at.update(&alice, &company, graph).await?;
+    let _: Vec<(WorksAt, Company)> = WorksAt::outgoing(&alice, graph).await?;
+    let _: Vec<(WorksAt, Person)> = WorksAt::incoming(&company, graph).await?;
+    WorksAt::disconnect(&alice, &company, graph).await?;
+    Ok(())
+}
+
+fn node(labels: &[&str], properties: Vec<(&str, BoltType)>) -> Node {
+    let labels: Vec<BoltType> = labels.iter().map(|label| (*label).into()).collect();
+    let properties: BoltMap = properties
+        .into_iter()
+        .map(|(key, value)| (key.into(), value))
+        .collect();
+    Node::new(BoltNode::new(1.into(), BoltList::from(labels), properties))
+}
+
+#[test]
+fn should_read_labels_from_nodes() {
+    let node = node(
+        &["Person"],
+        vec![
+            ("id", 1.into()),
+            ("firstName", "Alice".into()),
+            ("status", "suspended".into()),
+            ("email", "alice@example.com".into()),
+            ("street", "Main Street".into()),
+            ("city", "Springfield".into()),
+        ],
+    );
+
+    let person = Person::try_from(node).unwrap();
+
+    assert_eq!(
+        person,
+        Person {
+            id: 1,
+            first_name: "Alice".to_owned(),
+            age: None,
+            status: Status::Suspended,
+            email: Email("alice@example.com".to_owned()),
+            address: Address {
+                street: "Main Street".to_owned(),
+                city: "Springfield".to_owned(),
+            },
+            employer: None,
+            managers: vec![],
+        }
+    );
+}
+
+#[test]
+fn should_name_the_property_which_cant_be_converted() {
+    let node = node(
+        &["Person"],
+        vec![("id", 1.into()), ("firstName", 42.into())],
+    );
+
+    match Person::try_from(node) {
+        Err(Error::InvalidProperty {
+            target,
+            field,
+            property,
+            ..
+        }) => assert_eq!(
+            (target.as_str(), field.as_str(), property.as_str()),
+            ("Person", "first_name", "firstName")
+        ),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn should_read_skipped_fields_as_their_default() {
+    let node = node(
+        &["User"],
+        vec![
+            ("org", "acme".into()),
+            ("e-mail", "bob@acme.com".into()),
+            ("joined_at", 1.into()),
+            ("seen_at", 2.into()),
+        ],
+    );
+
+    let user = User::try_from(node).unwrap();
+
+    assert_eq!(
+        user,
+        User {
+            org: "acme".to_owned(),
+            email: "bob@acme.com".to_owned(),
+            joined_at: 1,
+            seen_at: 2,
+            nickname: None,
+            cache: vec![],
+            score: 10,
+        }
+    );
+    let key: BoltMap = vec![
+        ("org".into(), "acme".into()),
+        ("e-mail".into(), "bob@acme.com".into()),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(<User as NodeEntity>::key(&user), key);
+}
+
+#[test]
+fn should_map_tuple_structs_by_index() {
+    let node = node(
+        &["Code"],
+        vec![("0", "A1".into()), ("label", "first".into())],
+    );
+
+    assert_eq!(
+        Code::try_from(node).unwrap(),
+        Code("A1".to_owned(), Some("first".to_owned()))
+    );
+}
+
+#[test]
+fn should_find_nodes_with_typed_conditions() {
+    let finder = Person::find()
+        .where_age_gte(18)
+        .where_first_name_starts_with("A")
+        .order_by_first_name()
+        .include_managers()
+        .into_inner();
+
+    assert_eq!(
+        finder.to_string(),
+        "match (n:Person) where n.age >= $p0 and n.firstName starts with $p1 return n, [(n)<-[:MANAGES]-(m:Person) | m] as managers order by n.firstName asc"
+    );
+    assert_eq!(<Person as NodeEntity>::LABELS, &["Person"]);
+}
+
+#[test]
+fn should_hide_soft_deleted_nodes() {
+    let finder = Document::find().include_cited().into_inner();
+
+    assert_eq!(
+        finder.to_string(),
+        "match (n:Document) where n.deleted_at is null return n, [(n)-[:CITES]->(m:Document) where m.deleted_at is null | m] as cited"
+    );
+    assert_eq!(<Document as NodeEntity>::SOFT_DELETE, Some("deleted_at"));
+    assert_eq!(<Person as NodeEntity>::SOFT_DELETE, None);
+}
+
+#[test]
+fn should_read_generic_labels_from_nodes() {
+    let node = node(
+        &["Tagged"],
+        vec![("id", 1.into()), ("payload", "urgent".into())],
+    );
+
+    let tagged = Tagged::<String>::try_from(node).unwrap();
+
+    assert_eq!(
+        tagged,
+        Tagged {
+            id: 1,
+            payload: Some("urgent".to_owned()),
+        }
+    );
+}
+
+#[test]
+fn should_convert_fields_whose_type_mentions_option() {
+    let survey = node(
+        &["Survey"],
+        vec![
+            ("id", 1.into()),
+            ("answers", vec![3i64, -1].into()),
+            ("options", "all".into()),
+        ],
+    );
+    assert_eq!(
+        Survey::try_from(survey).unwrap(),
+        Survey {
+            id: 1,
+            answers: vec![Some(3), None],
+            options: Options("all".to_owned()),
+        }
+    );
+
+    let survey = node(
+        &["Survey"],
+        vec![("id", 1.into()), ("options", "all".into())],
+    );
+    assert!(Survey::try_from(survey).is_err());
+}
+
+#[test]
+fn should_convert_values() {
+    let status: BoltType = Status::Active.into();
+    assert_eq!(status, Into::<BoltType>::into("active"));
+    assert_eq!(Status::try_from(status).unwrap(), Status::Active);
+
+    let email: BoltType = Email("alice@example.com".to_owned()).into();
+    assert_eq!(
+        Email::try_from(email).unwrap(),
+        Email("alice@example.com".to_owned())
+    );
+}
+
+#[test]
+fn should_name_the_value_type_in_conversion_errors() {
+    match Status::try_from(Into::<BoltType>::into("deleted")) {
+        Err(Error::DeserializationError(msg)) => assert_eq!(
+            msg,
+            r#"unknown variant "deleted" of `Status`, expected one of "active", "suspended""#
+        ),
+        other => panic!("unexpected {:?}", other),
+    }
+    match Status::try_from(Into::<BoltType>::into(1)) {
+        Err(Error::DeserializationError(msg)) => {
+            assert!(msg.starts_with("expected `Status` as a string, got Integer"))
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    match Email::try_from(Into::<BoltType>::into(1)) {
+        Err(Error::InvalidValue { target, source }) => {
+            assert_eq!(target, "Email");
+            assert!(matches!(*source, Error::ConverstionError));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn should_build_queries_with_cypher() {
+    let name = "Alice";
+    let query = cypher!(
+        "match (p:Person {name: $name}) where p.age > $age return p limit $limit",
+        age = 18,
+        limit = 10,
+    );
+
+    assert_eq!(
+        query,
+        Query::new("match (p:Person {name: $name}) where p.age > $age return p limit $limit")
+            .param("name", name)
+            .param("age", 18)
+            .param("limit", 10)
+    );
+}
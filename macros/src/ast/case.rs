@@ -0,0 +1,210 @@
+//! Case conversions for `#[neo4j(rename_all = "...")]`.
+//! CREDIT: serde
+use std::fmt::{self, Display};
+
+/// The different possible ways to change case of fields in a struct.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RenameRule {
+    /// Don't apply a default rename rule.
+    None,
+    /// Rename fields to "lowercase" style.
+    LowerCase,
+    /// Rename fields to "UPPERCASE" style.
+    UpperCase,
+    /// Rename fields to "PascalCase" style.
+    PascalCase,
+    /// Rename fields to "camelCase" style.
+    CamelCase,
+    /// Rename fields to "snake_case" style.
+    SnakeCase,
+    /// Rename fields to "SCREAMING_SNAKE_CASE" style.
+    ScreamingSnakeCase,
+    /// Rename fields to "kebab-case" style.
+    KebabCase,
+    /// Rename fields to "SCREAMING-KEBAB-CASE" style.
+    ScreamingKebabCase,
+}
+
+static RENAME_RULES: &[(&str, RenameRule)] = &[
+    ("lowercase", RenameRule::LowerCase),
+    ("UPPERCASE", RenameRule::UpperCase),
+    ("PascalCase", RenameRule::PascalCase),
+    ("camelCase", RenameRule::CamelCase),
+    ("snake_case", RenameRule::SnakeCase),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnakeCase),
+    ("kebab-case", RenameRule::KebabCase),
+    ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebabCase),
+];
+
+impl RenameRule {
+    pub fn from_str(rename_all_str: &str) -> Result<Self, ParseError<'_>> {
+        RENAME_RULES
+            .iter()
+            .find(|(name, _)| *name == rename_all_str)
+            .map(|(_, rule)| *rule)
+            .ok_or(ParseError {
+                unknown: rename_all_str,
+            })
+    }
+
+    /// Apply a renaming rule to an enum variant, which is expected to be in PascalCase.
+    pub fn apply_to_variant(&self, variant: &str) -> String {
+        match *self {
+            RenameRule::None | RenameRule::PascalCase => variant.to_owned(),
+            RenameRule::LowerCase => variant.to_ascii_lowercase(),
+            RenameRule::UpperCase => variant.to_ascii_uppercase(),
+            RenameRule::CamelCase => variant[..1].to_ascii_lowercase() + &variant[1..],
+            RenameRule::SnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnakeCase => RenameRule::SnakeCase
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameRule::KebabCase => RenameRule::SnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameRule::ScreamingKebabCase => RenameRule::ScreamingSnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Apply a renaming rule to a struct field, which is expected to be in snake_case.
+    pub fn apply_to_field(&self, field: &str) -> String {
+        match *self {
+            RenameRule::None | RenameRule::SnakeCase => field.to_owned(),
+            RenameRule::LowerCase => field.replace('_', ""),
+            RenameRule::UpperCase => field.replace('_', "").to_ascii_uppercase(),
+            RenameRule::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::CamelCase => {
+                let pascal = RenameRule::PascalCase.apply_to_field(field);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            }
+            RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::ScreamingKebabCase => RenameRule::ScreamingSnakeCase
+                .apply_to_field(field)
+                .replace('_', "-"),
+        }
+    }
+}
+
+pub struct ParseError<'a> {
+    unknown: &'a str,
+}
+
+impl<'a> Display for ParseError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown rename rule `rename_all = ")?;
+        write!(f, "{:?}", self.unknown)?;
+        f.write_str("`, expected one of ")?;
+        for (i, (name, _rule)) in RENAME_RULES.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{:?}", name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenameRule::*;
+    use super::*;
+
+    #[test]
+    fn should_rename_fields() {
+        for &(original, lower, upper, camel, pascal, screaming, kebab, screaming_kebab) in &[
+            (
+                "outcome", "outcome", "OUTCOME", "outcome", "Outcome", "OUTCOME", "outcome",
+                "OUTCOME",
+            ),
+            (
+                "very_tasty",
+                "verytasty",
+                "VERYTASTY",
+                "veryTasty",
+                "VeryTasty",
+                "VERY_TASTY",
+                "very-tasty",
+                "VERY-TASTY",
+            ),
+            ("a", "a", "A", "a", "A", "A", "a", "A"),
+            ("z42", "z42", "Z42", "z42", "Z42", "Z42", "z42", "Z42"),
+        ] {
+            assert_eq!(None.apply_to_field(original), original);
+            assert_eq!(LowerCase.apply_to_field(original), lower);
+            assert_eq!(UpperCase.apply_to_field(original), upper);
+            assert_eq!(PascalCase.apply_to_field(original), pascal);
+            assert_eq!(CamelCase.apply_to_field(original), camel);
+            assert_eq!(SnakeCase.apply_to_field(original), original);
+            assert_eq!(ScreamingSnakeCase.apply_to_field(original), screaming);
+            assert_eq!(KebabCase.apply_to_field(original), kebab);
+            assert_eq!(ScreamingKebabCase.apply_to_field(original), screaming_kebab);
+        }
+    }
+
+    #[test]
+    fn should_rename_variants() {
+        for &(original, lower, upper, camel, snake, screaming, kebab, screaming_kebab) in &[
+            (
+                "Outcome", "outcome", "OUTCOME", "outcome", "outcome", "OUTCOME", "outcome",
+                "OUTCOME",
+            ),
+            (
+                "VeryTasty",
+                "verytasty",
+                "VERYTASTY",
+                "veryTasty",
+                "very_tasty",
+                "VERY_TASTY",
+                "very-tasty",
+                "VERY-TASTY",
+            ),
+            ("A", "a", "A", "a", "a", "A", "a", "A"),
+            ("Z42", "z42", "Z42", "z42", "z42", "Z42", "z42", "Z42"),
+        ] {
+            assert_eq!(None.apply_to_variant(original), original);
+            assert_eq!(LowerCase.apply_to_variant(original), lower);
+            assert_eq!(UpperCase.apply_to_variant(original), upper);
+            assert_eq!(PascalCase.apply_to_variant(original), original);
+            assert_eq!(CamelCase.apply_to_variant(original), camel);
+            assert_eq!(SnakeCase.apply_to_variant(original), snake);
+            assert_eq!(ScreamingSnakeCase.apply_to_variant(original), screaming);
+            assert_eq!(KebabCase.apply_to_variant(original), kebab);
+            assert_eq!(
+                ScreamingKebabCase.apply_to_variant(original),
+                screaming_kebab
+            );
+        }
+    }
+
+    #[test]
+    fn should_parse_rename_rules() {
+        assert_eq!(RenameRule::from_str("camelCase").ok(), Some(CamelCase));
+        let error = RenameRule::from_str("camel").err().unwrap().to_string();
+        assert!(error.starts_with("unknown rename rule `rename_all = \"camel\"`"));
+    }
+}
@@ -3,6 +3,8 @@ mod attrs;
 
 use super::{Ctx, Data, Derive};
 pub use attrs::ContainerAttrs;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
 
 /// A source data structure annotated with `#[derive(Serialize)]` and/or `#[derive(Deserialize)]`,
 /// parsed into an internal representation.
@@ -20,12 +22,8 @@ pub struct Container<'a> {
 }
 
 impl<'a> Container<'a> {
-    pub fn from_ast(
-        cx: &Ctx,
-        item: &'a syn::DeriveInput,
-        _derive: Derive,
-    ) -> Option<Container<'a>> {
-        let attrs = ContainerAttrs::from_ast(cx, item);
+    pub fn from_ast(cx: &Ctx, item: &'a syn::DeriveInput, derive: Derive) -> Option<Container<'a>> {
+        let attrs = ContainerAttrs::from_ast(cx, item, derive);
 
         let data = match &item.data {
             syn::Data::Enum(_) => {
@@ -33,7 +31,7 @@ impl<'a> Container<'a> {
                 return None;
             }
             syn::Data::Struct(data) => {
-                let (style, fields) = Data::from_struct(cx, &data.fields, attrs.default());
+                let (style, fields) = Data::from_struct(cx, &data.fields, &attrs);
                 Data::Struct(style, fields)
             }
             syn::Data::Union(_) => {
@@ -42,6 +40,21 @@ impl<'a> Container<'a> {
             }
         };
 
+        let mut names: Vec<&str> = attrs.labels().iter().map(String::as_str).collect();
+        names.push(attrs.relationship_type());
+        names.extend(attrs.soft_delete());
+        if !names.iter().all(|name| is_identifier(name)) {
+            let msg = "labels, relationship types and properties can't be empty nor contain the null character";
+            cx.error_spanned_by(item, msg);
+        }
+        let Data::Struct(_, fields) = &data;
+        for field in fields {
+            if field.attrs.is_property() && !is_identifier(field.attrs.name()) {
+                let msg = "properties can't be empty nor contain the null character";
+                cx.error_spanned_by(field.original, msg);
+            }
+        }
+
         let item = Container {
             ident: item.ident.clone(),
             attrs,
@@ -57,4 +70,83 @@ impl<'a> Container<'a> {
     pub fn ident(&self) -> &syn::Ident {
         &self.ident
     }
+
+    /// Whether the struct has lifetime parameters, its fields can then only be written.
+    pub fn is_borrowed(&self) -> bool {
+        self.generics.lifetimes().next().is_some()
+    }
+
+    /// The generics of the generated impls: those of the struct, along with the bounds the
+    /// generated code needs on the types of the fields using a type parameter, e.g.
+    /// `T: Clone + Into<BoltType>` and `T: TryFrom<BoltType>` for a `T` property.
+    pub fn impl_generics(&self) -> syn::Generics {
+        let mut generics = self.generics.clone();
+        let params = self
+            .generics
+            .type_params()
+            .map(|param| param.ident.clone())
+            .collect::<Vec<_>>();
+        if params.is_empty() {
+            return generics;
+        }
+
+        let Data::Struct(_, fields) = &self.data;
+        let mut bounds: Vec<TokenStream> = vec![];
+        for field in fields.iter().filter(|field| !field.attrs.skip()) {
+            if field.attrs.is_flattened() {
+                if mentions(field.ty, &params) {
+                    let ty = field.ty;
+                    bounds.push(quote!(#ty: neo4jrs::Properties));
+                }
+            } else if field.attrs.relation().is_some() {
+                match field.related_ty() {
+                    Some((ty, _)) if mentions(ty, &params) => {
+                        bounds.push(quote!(#ty: neo4jrs::NodeEntity))
+                    }
+                    _ => {}
+                }
+            } else if mentions(field.ty, &params) {
+                let (ty, value_ty) = (field.ty, field.value_ty());
+                if field.attrs.serialize_with().is_none() {
+                    bounds.push(quote! {
+                        #ty: std::clone::Clone + std::convert::Into<neo4jrs::types::BoltType>
+                    });
+                    // The finders and `delete_where_<field>` take the value of `Option<T>` fields
+                    if value_ty != ty {
+                        bounds.push(quote! {
+                            #value_ty: std::clone::Clone + std::convert::Into<neo4jrs::types::BoltType>
+                        });
+                    }
+                }
+                if field.attrs.deserialize_with().is_none() && !self.is_borrowed() {
+                    bounds.push(quote! {
+                        #value_ty: std::convert::TryFrom<neo4jrs::types::BoltType>
+                    });
+                }
+            }
+        }
+
+        let where_clause = generics.make_where_clause();
+        for bound in bounds {
+            where_clause.predicates.push(syn::parse_quote!(#bound));
+        }
+        generics
+    }
+}
+
+/// Whether the type uses one of the type parameters
+fn mentions(ty: &syn::Type, params: &[syn::Ident]) -> bool {
+    fn walk(tokens: TokenStream, params: &[syn::Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => params.contains(&ident),
+            TokenTree::Group(group) => walk(group.stream(), params),
+            _ => false,
+        })
+    }
+    walk(ty.to_token_stream(), params)
+}
+
+/// Whether the name can be written in a query, quoted if need be
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && !name.contains('\0')
 }
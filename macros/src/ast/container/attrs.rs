@@ -1,27 +1,63 @@
-// #![allow(dead_code)]
+use super::super::{
+    get_neo4j_meta_items, lit, Attr, BoolAttr, Ctx, Default, Derive, RenameRule, Symbol,
+    CHUNK_SIZE, DEFAULT, DETACH_DELETE, FROM, IDENTIFER, LABEL, LABELS, RENAME_ALL, SOFT_DELETE,
+    TO, TYPE,
+};
 
-use super::super::{get_neo4j_meta_items, lit, Attr, Ctx, Default, DEFAULT, EXPECTING, IDENTIFER};
-
-use syn::Meta::{NameValue, Path};
+use syn::Meta::{List, NameValue, Path};
 use syn::NestedMeta::{Lit, Meta};
 
 /// Represents struct or enum attribute information.
 pub struct ContainerAttrs {
     pub name: String,
-    /// The main identifier key to search with in database
-    pub identifier: String,
+    /// The fields identifying a node in the database
+    pub identifiers: Vec<String>,
     /// Default alternative for missing fields
     pub default: Default,
-    /// Error message generated when type can't be processed
-    expecting: Option<String>,
+    /// Labels of the node, the struct name unless set with `label` or `labels`
+    labels: Vec<String>,
+    /// Rule applied to the names of fields without `rename`
+    rename_all: RenameRule,
+    /// Number of structs sent per query by `insert_many` and `upsert_many`
+    chunk_size: usize,
+    /// Whether deleting a node also deletes its relationships
+    detach_delete: bool,
+    /// Property set when a node is deleted, instead of deleting it
+    soft_delete: Option<String>,
+    /// Type of the relationship, the struct name in SCREAMING_SNAKE_CASE unless set with `type`
+    relationship_type: String,
+    /// Start node of the relationship
+    from: Option<syn::Path>,
+    /// End node of the relationship
+    to: Option<syn::Path>,
 }
 
+/// Attributes only used by `#[derive(Label)]`
+const LABEL_ATTRS: &[Symbol] = &[
+    LABEL,
+    LABELS,
+    IDENTIFER,
+    CHUNK_SIZE,
+    DETACH_DELETE,
+    SOFT_DELETE,
+];
+
+/// Attributes only used by `#[derive(Relationship)]`
+const RELATIONSHIP_ATTRS: &[Symbol] = &[TYPE, FROM, TO];
+
 impl ContainerAttrs {
     /// Extract out the `#[neo4j(...)]` attributes from an item.
-    pub(crate) fn from_ast(cx: &Ctx, input: &syn::DeriveInput) -> Self {
-        let mut identifier: Attr<String> = Attr::none(cx, IDENTIFER);
+    pub(crate) fn from_ast(cx: &Ctx, input: &syn::DeriveInput, derive: Derive) -> Self {
+        let mut identifiers: Attr<Vec<String>> = Attr::none(cx, IDENTIFER);
         let mut default: Attr<Default> = Attr::none(cx, DEFAULT);
-        let expecting: Attr<String> = Attr::none(cx, EXPECTING);
+        let mut labels: Attr<Vec<String>> = Attr::none(cx, LABELS);
+        let mut rename_all: Attr<RenameRule> = Attr::none(cx, RENAME_ALL);
+        let mut chunk_size: Attr<usize> = Attr::none(cx, CHUNK_SIZE);
+        let mut relationship_type: Attr<String> = Attr::none(cx, TYPE);
+        let mut from: Attr<syn::Path> = Attr::none(cx, FROM);
+        let mut to: Attr<syn::Path> = Attr::none(cx, TO);
+        let mut detach_delete = BoolAttr::none(cx, DETACH_DELETE);
+        let mut soft_delete: Attr<String> = Attr::none(cx, SOFT_DELETE);
         let items = input
             .attrs
             .iter()
@@ -29,6 +65,23 @@ impl ContainerAttrs {
             .flatten();
 
         for item in items {
+            if let Meta(meta) = &item {
+                let (unsupported, derived): (&[&[Symbol]], _) = match derive {
+                    Derive::Label => (&[RELATIONSHIP_ATTRS], "Label"),
+                    Derive::Relation => (&[LABEL_ATTRS], "Relationship"),
+                    Derive::Properties => (&[LABEL_ATTRS, RELATIONSHIP_ATTRS], "Properties"),
+                };
+                let mut unsupported = unsupported.iter().copied().flatten();
+                if let Some(symbol) = unsupported.find(|symbol| meta.path() == **symbol) {
+                    let msg = format!(
+                        "neo4j attribute `{}` is not supported by #[derive({})]",
+                        symbol, derived
+                    );
+                    cx.error_spanned_by(meta.path(), msg);
+                    continue;
+                }
+            }
+
             match &item {
                 // Parse `#[neo4j(default)]`
                 Meta(Path(word)) if word == DEFAULT => {
@@ -38,9 +91,74 @@ impl ContainerAttrs {
                 Meta(NameValue(m)) if m.path == DEFAULT => {
                     default.set(&m.path, Default::from_container_name_value(&input, cx, m))
                 }
+                // Parse `#[neo4j(identifier = "...")]`
                 Meta(NameValue(m)) if m.path == IDENTIFER => {
-                    identifier.set_opt(&m.path, lit::to_string(&m.lit))
+                    identifiers.set_opt(&m.path, lit::to_string(&m.lit).map(|id| vec![id]))
                 }
+                // Parse `#[neo4j(identifier = ["...", ...])]`
+                Meta(List(m)) if m.path == IDENTIFER => identifiers.set(
+                    &m.path,
+                    strings(cx, m, "expected the field name as a string"),
+                ),
+                // Parse `#[neo4j(label = "...")]`
+                Meta(NameValue(m)) if m.path == LABEL => match lit::to_string(&m.lit) {
+                    Some(label) => labels.set(&m.path, vec![label]),
+                    None => cx.error_spanned_by(&m.lit, "expected the label as a string"),
+                },
+                // Parse `#[neo4j(labels = ["...", ...])]`
+                Meta(List(m)) if m.path == LABELS => {
+                    labels.set(&m.path, strings(cx, m, "expected the label as a string"))
+                }
+                // Parse `#[neo4j(rename_all = "...")]`
+                Meta(NameValue(m)) if m.path == RENAME_ALL => match lit::to_string(&m.lit) {
+                    Some(rule) => match RenameRule::from_str(&rule) {
+                        Ok(rule) => rename_all.set(&m.path, rule),
+                        Err(err) => cx.error_spanned_by(&m.lit, err),
+                    },
+                    None => cx.error_spanned_by(&m.lit, "expected the rename rule as a string"),
+                },
+                // Parse `#[neo4j(chunk_size = ...)]`
+                Meta(NameValue(m)) if m.path == CHUNK_SIZE => match &m.lit {
+                    syn::Lit::Int(int) => match int.base10_parse::<usize>() {
+                        Ok(size) if size > 0 => chunk_size.set(&m.path, size),
+                        _ => cx.error_spanned_by(int, "expected a positive chunk size"),
+                    },
+                    lit => cx.error_spanned_by(lit, "expected the chunk size as an integer"),
+                },
+                // Parse `#[neo4j(detach_delete)]`
+                Meta(Path(word)) if word == DETACH_DELETE => detach_delete.set_true(word),
+                // Parse `#[neo4j(soft_delete)]`
+                Meta(Path(word)) if word == SOFT_DELETE => {
+                    soft_delete.set(word, "deleted_at".to_owned())
+                }
+                // Parse `#[neo4j(soft_delete = "...")]`
+                Meta(NameValue(m)) if m.path == SOFT_DELETE => match lit::to_string(&m.lit) {
+                    Some(property) => soft_delete.set(&m.path, property),
+                    None => cx.error_spanned_by(&m.lit, "expected the property as a string"),
+                },
+                // Parse `#[neo4j(type = "...")]`
+                Meta(NameValue(m)) if m.path == TYPE => match lit::to_string(&m.lit) {
+                    Some(name) => relationship_type.set(&m.path, name),
+                    None => cx.error_spanned_by(&m.lit, "expected the type as a string"),
+                },
+                // Parse `#[neo4j(from = Type)]`
+                Meta(List(m)) if m.path == FROM => {
+                    from.set_opt(&m.path, node_type(cx, m, "expected the start node type"))
+                }
+                // Parse `#[neo4j(from = "Type")]`
+                Meta(NameValue(m)) if m.path == FROM => from.set_opt(
+                    &m.path,
+                    quoted_node_type(cx, m, "expected the start node type"),
+                ),
+                // Parse `#[neo4j(to = Type)]`
+                Meta(List(m)) if m.path == TO => {
+                    to.set_opt(&m.path, node_type(cx, m, "expected the end node type"))
+                }
+                // Parse `#[neo4j(to = "Type")]`
+                Meta(NameValue(m)) if m.path == TO => to.set_opt(
+                    &m.path,
+                    quoted_node_type(cx, m, "expected the end node type"),
+                ),
                 Lit(lit) => {
                     let msg = "unexpected literal in neo4j container attribute";
                     cx.error_spanned_by(lit, msg);
@@ -54,30 +172,134 @@ impl ContainerAttrs {
 
         Self {
             name: input.ident.to_string(),
-            identifier: identifier.get().unwrap_or("id".into()),
-            expecting: expecting.get(),
+            identifiers: identifiers.get().unwrap_or_else(|| vec!["id".into()]),
             default: default.get().unwrap_or(Default::None),
+            labels: labels
+                .get()
+                .unwrap_or_else(|| vec![input.ident.to_string()]),
+            rename_all: rename_all.get().unwrap_or(RenameRule::None),
+            chunk_size: chunk_size.get().unwrap_or(1000),
+            detach_delete: detach_delete.get(),
+            soft_delete: soft_delete.get(),
+            relationship_type: relationship_type
+                .get()
+                .unwrap_or_else(|| screaming_snake_case(&input.ident.to_string())),
+            from: from.get(),
+            to: to.get(),
         }
     }
 
-    /// Error message generated when type can't be deserialized.
-    /// If `None`, default message will be used
-    pub fn expecting(&self) -> Option<&str> {
-        self.expecting.as_ref().map(String::as_ref)
-    }
-
     /// Get a reference to the container's name.
     pub fn name(&self) -> &str {
         self.name.as_ref()
     }
 
-    /// Get a reference to the container's index.
-    pub fn identifier(&self) -> &str {
-        self.identifier.as_ref()
+    /// Get the names of the fields identifying a node.
+    pub fn identifiers(&self) -> &[String] {
+        &self.identifiers
     }
 
     /// Get a reference to the container's default.
     pub fn default(&self) -> &Default {
         &self.default
     }
+
+    /// Get the node labels.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Get the number of structs sent per query by bulk operations.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Get whether deleting a node also deletes its relationships.
+    pub fn detach_delete(&self) -> bool {
+        self.detach_delete
+    }
+
+    /// Get the property marking deleted nodes, if they are soft deleted.
+    pub fn soft_delete(&self) -> Option<&str> {
+        self.soft_delete.as_deref()
+    }
+
+    /// Get the relationship type.
+    pub fn relationship_type(&self) -> &str {
+        &self.relationship_type
+    }
+
+    /// Get the start node type of the relationship, if set.
+    pub fn from(&self) -> Option<&syn::Path> {
+        self.from.as_ref()
+    }
+
+    /// Get the end node type of the relationship, if set.
+    pub fn to(&self) -> Option<&syn::Path> {
+        self.to.as_ref()
+    }
+
+    /// Get the rule renaming fields.
+    pub fn rename_all(&self) -> RenameRule {
+        self.rename_all
+    }
+}
+
+/// The strings of `name = [...]` or `name(...)`, there must be at least one
+fn strings(cx: &Ctx, list: &syn::MetaList, msg: &str) -> Vec<String> {
+    let values = list
+        .nested
+        .iter()
+        .filter_map(|nested| match nested {
+            Lit(lit) => lit::to_string(lit).or_else(|| {
+                cx.error_spanned_by(lit, msg);
+                None
+            }),
+            other => {
+                cx.error_spanned_by(other, msg);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        cx.error_spanned_by(list, "expected at least one value");
+    }
+    values
+}
+
+/// The type of `name = Type`, parsed as `name(Type)`
+fn node_type(cx: &Ctx, list: &syn::MetaList, msg: &str) -> Option<syn::Path> {
+    match list.nested.iter().collect::<Vec<_>>().as_slice() {
+        [Meta(Path(path))] => Some(path.clone()),
+        _ => {
+            cx.error_spanned_by(list, msg);
+            None
+        }
+    }
+}
+
+/// The type of `name = "Type"`
+fn quoted_node_type(cx: &Ctx, m: &syn::MetaNameValue, msg: &str) -> Option<syn::Path> {
+    match &m.lit {
+        syn::Lit::Str(s) => s.parse().map_err(|err| cx.syn_error(err)).ok(),
+        lit => {
+            cx.error_spanned_by(lit, msg);
+            None
+        }
+    }
+}
+
+/// `FollowedBy` to `FOLLOWED_BY`, the usual case of relationship types
+fn screaming_snake_case(name: &str) -> String {
+    let mut screaming = String::new();
+    let mut previous = None;
+    for ch in name.chars() {
+        if ch.is_uppercase() && previous.map_or(false, |c: char| c.is_lowercase() || c.is_numeric())
+        {
+            screaming.push('_');
+        }
+        screaming.push(ch.to_ascii_uppercase());
+        previous = Some(ch);
+    }
+    screaming
 }
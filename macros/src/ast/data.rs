@@ -1,5 +1,5 @@
 #![allow(dead_code)]
-use super::{Ctx, Field, FieldAttrs};
+use super::{ContainerAttrs, Ctx, Field, FieldAttrs};
 use quote::ToTokens;
 use syn::punctuated::Punctuated;
 use syn::Token;
@@ -28,20 +28,20 @@ impl<'a> Data<'a> {
     pub fn from_struct(
         cx: &Ctx,
         fields: &'a syn::Fields,
-        container_default: &super::Default,
+        container: &ContainerAttrs,
     ) -> (Style, Vec<Field<'a>>) {
         match fields {
             syn::Fields::Named(fields) => (
                 Style::Struct,
-                Self::fields_from_ast(cx, &fields.named, container_default),
+                Self::fields_from_ast(cx, &fields.named, container),
             ),
             syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => (
                 Style::Newtype,
-                Self::fields_from_ast(cx, &fields.unnamed, container_default),
+                Self::fields_from_ast(cx, &fields.unnamed, container),
             ),
             syn::Fields::Unnamed(fields) => (
                 Style::Tuple,
-                Self::fields_from_ast(cx, &fields.unnamed, container_default),
+                Self::fields_from_ast(cx, &fields.unnamed, container),
             ),
             syn::Fields::Unit => (Style::Unit, Vec::new()),
         }
@@ -50,7 +50,7 @@ impl<'a> Data<'a> {
     fn fields_from_ast(
         cx: &Ctx,
         fields: &'a Punctuated<syn::Field, Token![,]>,
-        container_default: &super::Default,
+        container: &ContainerAttrs,
     ) -> Vec<Field<'a>> {
         fields
             .iter()
@@ -96,7 +96,7 @@ impl<'a> Data<'a> {
                         Some(ident) => syn::Member::Named(ident.clone()),
                         None => syn::Member::Unnamed(i.into()),
                     },
-                    attrs: FieldAttrs::from_ast(cx, i, field, container_default),
+                    attrs: FieldAttrs::from_ast(cx, i, field, container),
                     ty: &field.ty,
                     ty_str,
                     original: field,
@@ -1,5 +1,6 @@
 mod attrs;
-pub use attrs::FieldAttrs;
+pub use attrs::{Direction, FieldAttrs, Managed, Policy};
+use syn::ext::IdentExt;
 
 /// A field of a struct.
 pub struct Field<'a> {
@@ -11,10 +12,61 @@ pub struct Field<'a> {
 }
 
 impl<'a> Field<'a> {
+    /// The name of the field in generated code, its ident without `r#`, or its index in a tuple
+    /// struct
+    pub fn member_name(&self) -> String {
+        match &self.member {
+            syn::Member::Named(ident) => ident.unraw().to_string(),
+            syn::Member::Unnamed(index) => index.index.to_string(),
+        }
+    }
+
+    /// Whether the field is an `Option<T>`
     pub fn is_optional(&self) -> bool {
-        self.ty_str
-            .as_ref()
-            .unwrap_or(&"".to_string())
-            .contains("Option")
+        wrapped(self.ty, "Option").is_some()
+    }
+
+    /// The type of the values of the field, `T` for `Option<T>`
+    pub fn value_ty(&self) -> &'a syn::Type {
+        wrapped(self.ty, "Option").unwrap_or(self.ty)
+    }
+
+    /// The type of the related nodes of a relation field, `T` for `Option<T>` or `Vec<T>`, and
+    /// whether there are many of them
+    pub fn related_ty(&self) -> Option<(&'a syn::Type, bool)> {
+        match wrapped(self.ty, "Option") {
+            Some(ty) => Some((ty, false)),
+            None => wrapped(self.ty, "Vec").map(|ty| (ty, true)),
+        }
+    }
+
+    /// Whether the values of the field are strings
+    pub fn is_string(&self) -> bool {
+        match super::ungroup(self.value_ty()) {
+            syn::Type::Path(syn::TypePath { path, .. }) => path.is_ident("String"),
+            syn::Type::Reference(syn::TypeReference { elem, .. }) => {
+                matches!(&**elem, syn::Type::Path(syn::TypePath { path, .. }) if path.is_ident("str"))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// `T` when the type is `Wrapper<T>`
+fn wrapped<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::Type> {
+    if let syn::Type::Path(syn::TypePath { path, qself: None }) = super::ungroup(ty) {
+        let segment = path.segments.last();
+        if let Some(syn::PathSegment {
+            ident,
+            arguments: syn::PathArguments::AngleBracketed(args),
+        }) = segment
+        {
+            if ident == wrapper && args.args.len() == 1 {
+                if let Some(syn::GenericArgument::Type(ty)) = args.args.first() {
+                    return Some(ty);
+                }
+            }
+        }
     }
+    None
 }
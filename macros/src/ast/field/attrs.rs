@@ -1,12 +1,90 @@
-use super::super::{get_neo4j_meta_items, Attr, BoolAttr, Ctx, Default, DEFAULT, SKIP};
+use super::super::{
+    get_neo4j_meta_items, lit, Attr, BoolAttr, ContainerAttrs, Ctx, Default, CREATED_AT, DEFAULT,
+    DESERIALIZE_WITH, DIRECTION, FLATTEN, FULLTEXT, INDEX, ON_CREATE, ON_MATCH, RELATION, RENAME,
+    REQUIRED, SERIALIZE_WITH, SKIP, UNIQUE, UPDATED_AT, VERSION, WITH,
+};
 use syn::Meta::{NameValue, Path};
 use syn::NestedMeta::{Lit, Meta};
 
 /// Represents field attribute information
 pub struct FieldAttrs {
+    /// Name of the property in the database
     name: String,
     skip: bool,
     default: Default,
+    /// When the property is written by `persist` and `upsert`
+    policy: Policy,
+    /// The relationship leading to the related nodes held by the field, instead of a property
+    relation: Option<Relation>,
+    /// Constraints and indexes on the property
+    schema: Schema,
+    /// Function converting the value to a `BoltType`, instead of `Into`
+    serialize_with: Option<syn::ExprPath>,
+    /// Function converting a `BoltType` to the value, instead of `TryFrom`
+    deserialize_with: Option<syn::ExprPath>,
+    /// The properties of the struct held by the field are those of the node
+    flatten: bool,
+    /// The property is maintained by the generated queries
+    managed: Option<Managed>,
+}
+
+/// Constraints and indexes created by `ensure_schema`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Schema {
+    /// `#[neo4j(unique)]`
+    pub unique: bool,
+    /// `#[neo4j(index)]`
+    pub index: bool,
+    /// `#[neo4j(fulltext)]`
+    pub fulltext: bool,
+    /// `#[neo4j(required)]`
+    pub required: bool,
+}
+
+impl Schema {
+    pub fn is_empty(&self) -> bool {
+        !(self.unique || self.index || self.fulltext || self.required)
+    }
+}
+
+/// A field holding the nodes related through `#[neo4j(relation = "...")]`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Relation {
+    /// Type of the relationships
+    pub name: String,
+    pub direction: Direction,
+}
+
+/// Direction of the relationships of a relation field, `#[neo4j(direction = "out")]`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Direction {
+    /// From this node to the related ones, `"out"`, the default
+    Outgoing,
+    /// From the related nodes to this one, `"in"`
+    Incoming,
+}
+
+/// A property maintained by the generated queries instead of written from the field
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Managed {
+    /// Incremented by every write, `update` only matches the version of the struct,
+    /// `#[neo4j(version)]`
+    Version,
+    /// Set to `datetime()` when the node is created, `#[neo4j(created_at)]`
+    CreatedAt,
+    /// Set to `datetime()` whenever the node is written, `#[neo4j(updated_at)]`
+    UpdatedAt,
+}
+
+/// When a field is written by a `MERGE`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Policy {
+    /// Whether the node is created or matched
+    Always,
+    /// Only when the node is created, `#[neo4j(on_create)]`
+    OnCreate,
+    /// Only when the node already exists, `#[neo4j(on_match)]`
+    OnMatch,
 }
 
 impl FieldAttrs {
@@ -14,15 +92,25 @@ impl FieldAttrs {
         cx: &Ctx,
         index: usize,
         field: &syn::Field,
-        _container_default: &Default,
+        container: &ContainerAttrs,
     ) -> Self {
         let mut skip = BoolAttr::none(cx, SKIP);
         let mut default = Attr::none(cx, DEFAULT);
-
-        let name = match &field.ident {
-            Some(ident) => ident.to_string(),
-            None => index.to_string(),
-        };
+        let mut rename = Attr::none(cx, RENAME);
+        let mut on_create = BoolAttr::none(cx, ON_CREATE);
+        let mut on_match = BoolAttr::none(cx, ON_MATCH);
+        let mut relation = Attr::none(cx, RELATION);
+        let mut direction = Attr::none(cx, DIRECTION);
+        let mut unique = BoolAttr::none(cx, UNIQUE);
+        let mut index_attr = BoolAttr::none(cx, INDEX);
+        let mut fulltext = BoolAttr::none(cx, FULLTEXT);
+        let mut required = BoolAttr::none(cx, REQUIRED);
+        let mut serialize_with = Attr::none(cx, SERIALIZE_WITH);
+        let mut deserialize_with = Attr::none(cx, DESERIALIZE_WITH);
+        let mut flatten = BoolAttr::none(cx, FLATTEN);
+        let mut version = BoolAttr::none(cx, VERSION);
+        let mut created_at = BoolAttr::none(cx, CREATED_AT);
+        let mut updated_at = BoolAttr::none(cx, UPDATED_AT);
 
         let items = field
             .attrs
@@ -47,6 +135,97 @@ impl FieldAttrs {
                     default.set(&m.path, Default::custom(m, cx, &m.lit))
                 }
 
+                // Parse `#[neo4j(rename = "...")]`
+                Meta(NameValue(m)) if m.path == RENAME => match lit::to_string(&m.lit) {
+                    Some(name) => rename.set(&m.path, name),
+                    None => cx.error_spanned_by(&m.lit, "expected the new name as a string"),
+                },
+
+                // Parse `#[neo4j(on_create)]`
+                Meta(Path(word)) if word == ON_CREATE => {
+                    on_create.set_true(word);
+                }
+
+                // Parse `#[neo4j(on_match)]`
+                Meta(Path(word)) if word == ON_MATCH => {
+                    on_match.set_true(word);
+                }
+
+                // Parse `#[neo4j(relation = "...")]`
+                Meta(NameValue(m)) if m.path == RELATION => match lit::to_string(&m.lit) {
+                    Some(name) => relation.set(&m.path, name),
+                    None => {
+                        cx.error_spanned_by(&m.lit, "expected the relationship type as a string")
+                    }
+                },
+
+                // Parse `#[neo4j(direction = "...")]`
+                Meta(NameValue(m)) if m.path == DIRECTION => {
+                    match lit::to_string(&m.lit).as_deref() {
+                        Some("out") => direction.set(&m.path, Direction::Outgoing),
+                        Some("in") => direction.set(&m.path, Direction::Incoming),
+                        _ => cx.error_spanned_by(&m.lit, "expected \"out\" or \"in\""),
+                    }
+                }
+
+                // Parse `#[neo4j(unique)]`
+                Meta(Path(word)) if word == UNIQUE => {
+                    unique.set_true(word);
+                }
+
+                // Parse `#[neo4j(index)]`
+                Meta(Path(word)) if word == INDEX => {
+                    index_attr.set_true(word);
+                }
+
+                // Parse `#[neo4j(fulltext)]`
+                Meta(Path(word)) if word == FULLTEXT => {
+                    fulltext.set_true(word);
+                }
+
+                // Parse `#[neo4j(required)]`
+                Meta(Path(word)) if word == REQUIRED => {
+                    required.set_true(word);
+                }
+
+                // Parse `#[neo4j(with = "...")]`
+                Meta(NameValue(m)) if m.path == WITH => {
+                    if let Some(module) = parse_path(cx, &m.lit) {
+                        serialize_with.set(&m.path, in_module(&module, "serialize"));
+                        deserialize_with.set(&m.path, in_module(&module, "deserialize"));
+                    }
+                }
+
+                // Parse `#[neo4j(serialize_with = "...")]`
+                Meta(NameValue(m)) if m.path == SERIALIZE_WITH => {
+                    serialize_with.set_opt(&m.path, parse_path(cx, &m.lit))
+                }
+
+                // Parse `#[neo4j(deserialize_with = "...")]`
+                Meta(NameValue(m)) if m.path == DESERIALIZE_WITH => {
+                    deserialize_with.set_opt(&m.path, parse_path(cx, &m.lit))
+                }
+
+                // Parse `#[neo4j(flatten)]`
+                Meta(Path(word)) if word == FLATTEN => {
+                    flatten.set_true(word);
+                }
+
+                // Parse `#[neo4j(version)]`
+                Meta(Path(word)) if word == VERSION => {
+                    version.set_true(word);
+                }
+
+                // Parse `#[neo4j(created_at)]`
+                Meta(Path(word)) if word == CREATED_AT => {
+                    created_at.set_true(word);
+                }
+
+                // Parse `#[neo4j(updated_at)]`
+                Meta(Path(word)) if word == UPDATED_AT => {
+                    updated_at.set_true(word);
+                }
+
                 Lit(lit) => {
                     cx.error_spanned_by(lit, "unexpected literal in neo4j container attribute");
                 }
@@ -59,10 +238,89 @@ impl FieldAttrs {
             }
         }
 
+        let name = rename.get().unwrap_or_else(|| match &field.ident {
+            Some(ident) => container
+                .rename_all()
+                .apply_to_field(ident.to_string().trim_start_matches("r#")),
+            None => index.to_string(),
+        });
+
+        let policy = match (on_create.get(), on_match.get()) {
+            (true, true) => {
+                let msg = "#[neo4j(on_create)] and #[neo4j(on_match)] can't be used together";
+                cx.error_spanned_by(field, msg);
+                Policy::Always
+            }
+            (true, false) => Policy::OnCreate,
+            (false, true) => Policy::OnMatch,
+            (false, false) => Policy::Always,
+        };
+
+        let relation = match (relation.get(), direction.get_with_tokens()) {
+            (Some(name), direction) => Some(Relation {
+                name,
+                direction: direction.map_or(Direction::Outgoing, |(_, direction)| direction),
+            }),
+            (None, Some((tokens, _))) => {
+                cx.error_spanned_by(tokens, "#[neo4j(direction)] requires #[neo4j(relation)]");
+                None
+            }
+            (None, None) => None,
+        };
+        if relation.is_some() && policy != Policy::Always {
+            let msg =
+                "relation fields are synced on every persist, they can't be on_create or on_match";
+            cx.error_spanned_by(field, msg);
+        }
+
+        let schema = Schema {
+            unique: unique.get(),
+            index: index_attr.get(),
+            fulltext: fulltext.get(),
+            required: required.get(),
+        };
+        let serialize_with = serialize_with.get();
+        let deserialize_with = deserialize_with.get();
+        if flatten.get()
+            && (relation.is_some()
+                || policy != Policy::Always
+                || !schema.is_empty()
+                || serialize_with.is_some()
+                || deserialize_with.is_some())
+        {
+            let msg = "#[neo4j(flatten)] can't be combined with relation, on_create, on_match, constraints, indexes or converters";
+            cx.error_spanned_by(field, msg);
+        }
+
+        let managed = match (version.get(), created_at.get(), updated_at.get()) {
+            (false, false, false) => None,
+            (true, false, false) => Some(Managed::Version),
+            (false, true, false) => Some(Managed::CreatedAt),
+            (false, false, true) => Some(Managed::UpdatedAt),
+            _ => {
+                let msg = "a field can only be one of #[neo4j(version)], #[neo4j(created_at)] or #[neo4j(updated_at)]";
+                cx.error_spanned_by(field, msg);
+                None
+            }
+        };
+        if managed.is_some()
+            && (skip.get() || flatten.get() || relation.is_some() || policy != Policy::Always)
+        {
+            let msg = "versions and timestamps are written by the generated queries, they can't be skip, flatten, relation, on_create or on_match";
+            cx.error_spanned_by(field, msg);
+        }
+
         Self {
             name,
             skip: skip.get(),
             default: default.get().unwrap_or(Default::None),
+            policy,
+            relation,
+            schema,
+            serialize_with,
+            deserialize_with,
+            flatten: flatten.get(),
+            managed,
         }
     }
 
@@ -80,4 +338,63 @@ impl FieldAttrs {
     pub fn default(&self) -> &Default {
         &self.default
     }
+
+    /// Get the relationship of a relation field.
+    pub fn relation(&self) -> Option<&Relation> {
+        self.relation.as_ref()
+    }
+
+    /// Whether the field is stored as a property of the node.
+    pub fn is_property(&self) -> bool {
+        !self.skip && !self.flatten && self.relation.is_none()
+    }
+
+    /// Whether the properties of the field are embedded in the node, and it isn't skipped.
+    pub fn is_flattened(&self) -> bool {
+        !self.skip && self.flatten
+    }
+
+    /// Get the function converting the value to a `BoltType`.
+    pub fn serialize_with(&self) -> Option<&syn::ExprPath> {
+        self.serialize_with.as_ref()
+    }
+
+    /// Get the function converting a `BoltType` to the value.
+    pub fn deserialize_with(&self) -> Option<&syn::ExprPath> {
+        self.deserialize_with.as_ref()
+    }
+
+    /// Get the constraints and indexes on the property.
+    pub fn schema(&self) -> Schema {
+        self.schema
+    }
+
+    /// Get whether the property is a version or a timestamp maintained by the generated queries.
+    pub fn managed(&self) -> Option<Managed> {
+        self.managed
+    }
+
+    /// Get when the field is written by a `MERGE`.
+    pub fn policy(&self) -> Policy {
+        self.policy
+    }
+}
+
+/// The function or module named by `name = "path"`
+fn parse_path(cx: &Ctx, lit: &syn::Lit) -> Option<syn::ExprPath> {
+    match lit {
+        syn::Lit::Str(s) => s.parse().map_err(|err| cx.syn_error(err)).ok(),
+        lit => {
+            cx.error_spanned_by(lit, "expected the path as a string");
+            None
+        }
+    }
+}
+
+/// The function of the module, e.g. `module::serialize`
+fn in_module(module: &syn::ExprPath, function: &str) -> syn::ExprPath {
+    let mut path = module.clone();
+    let ident = syn::Ident::new(function, proc_macro2::Span::call_site());
+    path.path.segments.push(ident.into());
+    path
 }
@@ -1,4 +1,5 @@
 mod attr;
+mod case;
 mod container;
 mod ctx;
 mod data;
@@ -9,6 +10,7 @@ pub mod lit;
 mod symbol;
 
 pub use attr::*;
+pub use case::RenameRule;
 pub use container::*;
 pub use ctx::Ctx;
 pub use data::*;
@@ -16,29 +18,22 @@ pub use default::Default;
 pub use derive::{ungroup, Derive};
 pub use field::*;
 pub use symbol::*;
-use syn::Meta::List;
+use syn::ext::IdentExt;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::{token, Meta, MetaList, NestedMeta, Token};
 
-pub fn iter_fields<T, F>(cx: &Ctx, cont: &Container, transform: F) -> Vec<T>
+/// Transforms the fields stored as properties
+pub fn iter_fields<T, F>(cont: &Container, transform: F) -> Vec<T>
 where
-    F: Fn(&Field) -> Option<T>,
+    F: FnMut(&Field) -> Option<T>,
 {
-    match &cont.data {
-        Data::Struct(Style::Struct, fields) => fields
-            .iter()
-            .map(|field| {
-                if field.attrs.skip() {
-                    None
-                } else {
-                    transform(field)
-                }
-            })
-            .flatten()
-            .collect(),
-        _ => {
-            cx.error_spanned_by(cont.ident(), "Only struct supported");
-            vec![]
-        }
-    }
+    let Data::Struct(_, fields) = &cont.data;
+    fields
+        .iter()
+        .filter(|field| field.attrs.is_property())
+        .filter_map(transform)
+        .collect()
 }
 
 pub fn get_neo4j_meta_items(cx: &Ctx, attr: &syn::Attribute) -> Result<Vec<syn::NestedMeta>, ()> {
@@ -46,15 +41,57 @@ pub fn get_neo4j_meta_items(cx: &Ctx, attr: &syn::Attribute) -> Result<Vec<syn::
         return Ok(Vec::new());
     }
 
-    match attr.parse_meta() {
-        Ok(List(meta)) => Ok(meta.nested.into_iter().collect()),
-        Ok(other) => {
-            cx.error_spanned_by(other, "expected #[neo4j(...)]");
-            Err(())
-        }
+    if attr.tokens.is_empty() {
+        cx.error_spanned_by(attr, "expected #[neo4j(...)]");
+        return Err(());
+    }
+
+    match attr.parse_args_with(parse_meta_items) {
+        Ok(items) => Ok(items),
         Err(err) => {
             cx.syn_error(err);
             Err(())
         }
     }
 }
+
+/// Parses the items of `#[neo4j(...)]`, where on top of the usual meta items, `name = [...]` and
+/// `name = path::to::Type` are accepted and represented the same way as `name(...)`.
+fn parse_meta_items(input: ParseStream) -> syn::Result<Vec<NestedMeta>> {
+    let mut items = Vec::new();
+    while !input.is_empty() {
+        if input.peek(syn::Ident) && input.peek2(Token![=]) && input.peek3(token::Bracket) {
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let content;
+            let bracket = syn::bracketed!(content in input);
+            let nested = Punctuated::<NestedMeta, Token![,]>::parse_terminated(&content)?;
+            items.push(NestedMeta::Meta(Meta::List(MetaList {
+                path: ident.into(),
+                paren_token: token::Paren(bracket.span),
+                nested,
+            })));
+        } else if input.peek(syn::Ident)
+            && input.peek2(Token![=])
+            && (input.peek3(syn::Ident::peek_any) || input.peek3(Token![::]))
+            && !input.peek3(syn::LitBool)
+        {
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let path: syn::Path = input.parse()?;
+            items.push(NestedMeta::Meta(Meta::List(MetaList {
+                path: ident.into(),
+                paren_token: token::Paren::default(),
+                nested: std::iter::once(NestedMeta::Meta(Meta::Path(path))).collect(),
+            })));
+        } else {
+            items.push(input.parse()?);
+        }
+
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+    Ok(items)
+}
@@ -4,13 +4,35 @@ use syn::{Ident, Path};
 #[derive(Copy, Clone)]
 pub struct Symbol(&'static str);
 
+pub const CHUNK_SIZE: Symbol = Symbol("chunk_size");
+pub const CREATED_AT: Symbol = Symbol("created_at");
 pub const DEFAULT: Symbol = Symbol("default");
-#[allow(dead_code)]
+pub const DETACH_DELETE: Symbol = Symbol("detach_delete");
+pub const DESERIALIZE_WITH: Symbol = Symbol("deserialize_with");
+pub const DIRECTION: Symbol = Symbol("direction");
+pub const FLATTEN: Symbol = Symbol("flatten");
+pub const FROM: Symbol = Symbol("from");
+pub const FULLTEXT: Symbol = Symbol("fulltext");
+pub const RELATION: Symbol = Symbol("relation");
 pub const RENAME: Symbol = Symbol("rename");
+pub const RENAME_ALL: Symbol = Symbol("rename_all");
+pub const SERIALIZE_WITH: Symbol = Symbol("serialize_with");
+pub const REQUIRED: Symbol = Symbol("required");
 pub const NEO4J: Symbol = Symbol("neo4j");
 pub const SKIP: Symbol = Symbol("skip");
+pub const SOFT_DELETE: Symbol = Symbol("soft_delete");
 pub const IDENTIFER: Symbol = Symbol("identifier");
-pub const EXPECTING: Symbol = Symbol("expecting");
+pub const INDEX: Symbol = Symbol("index");
+pub const LABEL: Symbol = Symbol("label");
+pub const LABELS: Symbol = Symbol("labels");
+pub const ON_CREATE: Symbol = Symbol("on_create");
+pub const ON_MATCH: Symbol = Symbol("on_match");
+pub const TO: Symbol = Symbol("to");
+pub const TYPE: Symbol = Symbol("type");
+pub const UNIQUE: Symbol = Symbol("unique");
+pub const UPDATED_AT: Symbol = Symbol("updated_at");
+pub const VERSION: Symbol = Symbol("version");
+pub const WITH: Symbol = Symbol("with");
 
 impl PartialEq<Symbol> for Ident {
     fn eq(&self, word: &Symbol) -> bool {
@@ -1,73 +1,65 @@
+use crate::ast::Ctx;
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{DeriveInput, MetaList};
+use quote::{format_ident, quote};
+use syn::DeriveInput;
 
 pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
+    let ctx = Ctx::new();
     let struct_name = &ast.ident;
-
-    let meta = ast.attrs.get(0).unwrap().parse_meta().unwrap();
-
-    let values: Vec<syn::LitInt> = match meta {
-        syn::Meta::List(MetaList { nested, .. }) => {
-            nested.into_iter().map(|nested_meta| match nested_meta {
-                syn::NestedMeta::Lit(syn::Lit::Int(value)) => value,
-                _ => panic!(concat!(
-                    stringify!(#struct_name),
-                    ": signature is not literal"
-                )),
-            })
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let values = signature(&ctx, &ast);
+    let fields = match &ast.data {
+        syn::Data::Struct(structure) => structure.fields.iter().collect::<Vec<_>>(),
+        _ => {
+            ctx.error_spanned_by(struct_name, "#[derive(BoltStruct)] only supports structs");
+            vec![]
         }
-        _ => panic!(concat!(stringify!(#struct_name), ": invalid signature")),
-    }
-    .collect();
-
-    let (struct_marker, struct_signature) = if values.len() == 2 {
-        let marker = values.get(0).unwrap();
-        let sig = values.get(1).unwrap();
-        (quote! { #marker}, quote! {Some(#sig)})
-    } else {
-        let marker = values.get(0).unwrap();
-        (quote! { #marker}, quote! { None::<u8> })
     };
 
-    let fields = if let syn::Data::Struct(structure) = ast.data {
-        match structure.fields {
-            syn::Fields::Named(syn::FieldsNamed { named, .. }) => named,
-            syn::Fields::Unnamed(_) => {
-                unimplemented!(concat!(stringify!(#name), ": unnamed fields not supported"))
-            }
-            syn::Fields::Unit => syn::punctuated::Punctuated::new(),
-        }
-    } else {
-        unimplemented!(concat!(stringify!(#name), ": not a struct"));
+    ctx.check()?;
+
+    let (struct_marker, struct_signature) = match values.as_slice() {
+        [marker, sig] => (quote! { #marker}, quote! {Some(#sig)}),
+        [marker] => (quote! { #marker}, quote! { None::<u8> }),
+        _ => unreachable!("checked by signature"),
     };
 
-    let serialize_fields = fields.iter().map(|f| {
-        let name = &f.ident;
+    // The fields are serialized into locals named after them, `field0`, ... for tuple structs
+    let members = fields
+        .iter()
+        .enumerate()
+        .map(|(index, f)| match &f.ident {
+            Some(ident) => (syn::Member::Named(ident.clone()), ident.clone()),
+            None => (
+                syn::Member::Unnamed(index.into()),
+                format_ident!("field{}", index),
+            ),
+        })
+        .collect::<Vec<_>>();
+
+    let serialize_fields = members.iter().map(|(member, name)| {
         quote! {
-            let #name: bytes::Bytes = self.#name.into_bytes(version)?
+            let #name: bytes::Bytes = self.#member.into_bytes(version)?
         }
     });
 
-    let allocate_bytes = fields.iter().map(|f| {
-        let name = &f.ident;
+    let allocate_bytes = members.iter().map(|(_, name)| {
         quote! {
             total_bytes += #name.len()
         }
     });
 
-    let put_bytes = fields.iter().map(|f| {
-        let name = &f.ident;
+    let put_bytes = members.iter().map(|(_, name)| {
         quote! {
             bytes.put(#name)
         }
     });
 
-    let deserialize_fields = fields.iter().map(|f| {
-        let name = &f.ident;
+    let deserialize_fields = members.iter().zip(&fields).map(|((member, _), f)| {
         let typ = &f.ty;
         quote! {
-            #name: #typ::parse(version, input.clone())?
+            #member: <#typ>::parse(version, input.clone())?
         }
     });
 
@@ -75,7 +67,7 @@ pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
         use std::convert::*;
         use bytes::*;
 
-        impl #struct_name {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
 
             pub fn into_bytes(self, version: crate::version::Version) -> crate::errors::Result<bytes::Bytes> {
                 #(#serialize_fields;)*
@@ -92,7 +84,7 @@ pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
 
         }
 
-        impl #struct_name {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
             pub fn can_parse(version: crate::version::Version, input: std::rc::Rc<std::cell::RefCell<bytes::Bytes>>) -> bool {
                 match (#struct_marker, #struct_signature) {
                     (marker, Some(signature)) =>  {
@@ -106,9 +98,9 @@ pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
             }
         }
 
-        impl #struct_name {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
 
-            pub fn parse(version: crate::version::Version, input: std::rc::Rc<std::cell::RefCell<bytes::Bytes>>) -> crate::errors::Result<#struct_name> {
+            pub fn parse(version: crate::version::Version, input: std::rc::Rc<std::cell::RefCell<bytes::Bytes>>) -> crate::errors::Result<Self> {
 
                 match (#struct_marker, #struct_signature) {
                     (_, Some(_)) =>  {
@@ -120,7 +112,7 @@ pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
                     }
                 }
 
-                Ok(#struct_name {
+                Ok(Self {
                     #(#deserialize_fields,)*
                 })
             }
@@ -129,5 +121,98 @@ pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
 
     };
 
-    Ok(expanded.into())
+    Ok(expanded)
+}
+
+/// The marker, and the signature if any, of `#[signature(marker)]` or
+/// `#[signature(marker, signature)]`
+fn signature(cx: &Ctx, ast: &DeriveInput) -> Vec<syn::LitInt> {
+    let msg = "expected #[signature(marker)] or #[signature(marker, signature)]";
+    let attr = match ast
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("signature"))
+    {
+        Some(attr) => attr,
+        None => {
+            cx.error_spanned_by(&ast.ident, msg);
+            return vec![];
+        }
+    };
+    let nested = match attr.parse_meta() {
+        Ok(syn::Meta::List(list)) => list.nested,
+        Ok(meta) => {
+            cx.error_spanned_by(meta, msg);
+            return vec![];
+        }
+        Err(err) => {
+            cx.syn_error(err);
+            return vec![];
+        }
+    };
+
+    let mut values = vec![];
+    for nested_meta in &nested {
+        match nested_meta {
+            syn::NestedMeta::Lit(syn::Lit::Int(value)) => values.push(value.clone()),
+            other => cx.error_spanned_by(other, "expected the signature as an integer literal"),
+        }
+    }
+    if values.is_empty() || values.len() > 2 {
+        cx.error_spanned_by(attr, msg);
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_errors(input: DeriveInput) -> Vec<String> {
+        expand(input)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn should_serialize_the_fields_of_tuple_structs() {
+        let expanded = expand(parse_quote! {
+            #[signature(0xB2, 0x01)]
+            struct Pair<T>(T, BoltString);
+        })
+        .unwrap()
+        .to_string();
+
+        assert!(expanded.contains("impl < T > Pair < T > {"));
+        assert!(expanded.contains(
+            "let field0 : bytes :: Bytes = self . 0 . into_bytes (version) ? ; let field1 : bytes :: Bytes = self . 1 . into_bytes (version) ? ;"
+        ));
+        assert!(expanded.contains(
+            "Ok (Self { 0 : < T > :: parse (version , input . clone ()) ? , 1 : < BoltString > :: parse (version , input . clone ()) ? , })"
+        ));
+    }
+
+    #[test]
+    fn should_report_invalid_signatures() {
+        assert_eq!(
+            expand_errors(parse_quote! {
+                struct Missing {}
+            }),
+            vec!["expected #[signature(marker)] or #[signature(marker, signature)]"]
+        );
+        assert_eq!(
+            expand_errors(parse_quote! {
+                #[signature("B1")]
+                enum Invalid {}
+            }),
+            vec![
+                "expected the signature as an integer literal",
+                "expected #[signature(marker)] or #[signature(marker, signature)]",
+                "#[derive(BoltStruct)] only supports structs",
+            ]
+        );
+    }
 }
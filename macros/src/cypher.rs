@@ -0,0 +1,237 @@
+//! `cypher!`, a query whose parameters are checked against the `$placeholders` at compile time.
+use crate::ast::Ctx;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Token};
+
+/// `"query", name = value, ...`
+pub struct Input {
+    query: LitStr,
+    bindings: Punctuated<Binding, Token![,]>,
+}
+
+/// `name = value`, or `"name" = value` for placeholders which aren't identifiers
+struct Binding {
+    name: String,
+    span: TokenStream,
+    value: Expr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let query = input.parse()?;
+        let bindings = if input.is_empty() {
+            Punctuated::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Punctuated::parse_terminated(input)?
+        };
+        Ok(Input { query, bindings })
+    }
+}
+
+impl Parse for Binding {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (name, span) = if input.peek(LitStr) {
+            let lit = input.parse::<LitStr>()?;
+            (lit.value(), lit.into_token_stream())
+        } else {
+            let ident = Ident::parse_any(input)?;
+            (ident.unraw().to_string(), ident.into_token_stream())
+        };
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Binding { name, span, value })
+    }
+}
+
+pub fn expand(input: Input) -> Result<TokenStream, Vec<syn::Error>> {
+    let ctx = Ctx::new();
+    let query = &input.query;
+    let placeholders = placeholders(&query.value());
+
+    let mut bound: Vec<&Binding> = vec![];
+    for binding in &input.bindings {
+        let name = &binding.name;
+        if bound.iter().any(|other| other.name == *name) {
+            let msg = format!("`{}` is bound more than once", name);
+            ctx.error_spanned_by(&binding.span, msg);
+        } else if !placeholders.contains(name) {
+            let msg = format!(
+                "`{}` isn't used by the query, there is no `${}`",
+                name, name
+            );
+            ctx.error_spanned_by(&binding.span, msg);
+        } else {
+            bound.push(binding);
+        }
+    }
+
+    let params = placeholders
+        .iter()
+        .filter_map(|placeholder| {
+            if let Some(binding) = bound.iter().find(|b| b.name == *placeholder) {
+                let value = &binding.value;
+                return Some(quote!(.param(#placeholder, #value)));
+            }
+            // Captures the variable named after the placeholder, like `format!`
+            match syn::parse_str::<Ident>(placeholder) {
+                Ok(_) => {
+                    let variable = Ident::new(placeholder, query.span());
+                    Some(quote!(.param(#placeholder, std::clone::Clone::clone(&#variable))))
+                }
+                Err(_) => {
+                    let msg = format!(
+                        "`${}` isn't bound, bind it with `\"{}\" = value`",
+                        placeholder, placeholder
+                    );
+                    ctx.error_spanned_by(query, msg);
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    ctx.check()?;
+
+    Ok(quote! {
+        neo4jrs::Query::new(#query) #(#params)*
+    })
+}
+
+/// The names of the `$placeholders` of the query, in order of first use, ignoring those in
+/// strings, comments and quoted names
+fn placeholders(query: &str) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                while let Some(next) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == c {
+                        break;
+                    }
+                }
+            }
+            '`' => skip_quoted_name(&mut chars),
+            '/' if chars.peek() == Some(&'/') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = None;
+                for next in chars.by_ref() {
+                    if previous == Some('*') && next == '/' {
+                        break;
+                    }
+                    previous = Some(next);
+                }
+            }
+            '$' => {
+                let mut name = String::new();
+                if chars.peek() == Some(&'`') {
+                    chars.next();
+                    while let Some(next) = chars.next() {
+                        if next == '`' {
+                            if chars.peek() != Some(&'`') {
+                                break;
+                            }
+                            chars.next();
+                        }
+                        name.push(next);
+                    }
+                } else {
+                    while let Some(&next) = chars.peek() {
+                        if !(next.is_alphanumeric() || next == '_') {
+                            break;
+                        }
+                        name.push(next);
+                        chars.next();
+                    }
+                }
+                if !name.is_empty() && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Skips a name quoted with backticks, where doubled backticks are escaped ones
+fn skip_quoted_name(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while let Some(next) = chars.next() {
+        if next == '`' {
+            if chars.peek() != Some(&'`') {
+                return;
+            }
+            chars.next();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_errors(input: Input) -> Vec<String> {
+        expand(input)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn should_find_placeholders_outside_strings_and_comments() {
+        let query = r#"
+            match (p:`Person $label`) // where p.name = $commented
+            where p.name = $name and p.bio <> '$bio \' $bio' and p.note <> "$note"
+            /* and p.age > $old */ and p.age > $age and p.`$key` = $`first name`
+            return p, $name, $0
+        "#;
+
+        assert_eq!(placeholders(query), vec!["name", "age", "first name", "0"]);
+    }
+
+    #[test]
+    fn should_bind_values_and_capture_variables() {
+        let expanded = expand(parse_quote! {
+            "match (p:Person {name: $name, age: $age}) return p limit $type skip $`0`", r#type = 10, name = user.name, "0" = 5,
+        })
+        .unwrap()
+        .to_string();
+
+        assert_eq!(
+            expanded,
+            r#"neo4jrs :: Query :: new ("match (p:Person {name: $name, age: $age}) return p limit $type skip $`0`") . param ("name" , user . name) . param ("age" , std :: clone :: Clone :: clone (& age)) . param ("type" , 10) . param ("0" , 5)"#
+        );
+    }
+
+    #[test]
+    fn should_report_unbound_and_unused_parameters() {
+        let errors = expand_errors(parse_quote! {
+            "match (p:Person {name: $name}) return p skip $0", name = "Alice", age = 30, "name" = "Bob"
+        });
+
+        assert_eq!(
+            errors,
+            vec![
+                "`age` isn't used by the query, there is no `$age`",
+                "`name` is bound more than once",
+                "`$0` isn't bound, bind it with `\"0\" = value`",
+            ]
+        );
+    }
+}
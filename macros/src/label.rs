@@ -1,12 +1,16 @@
-use crate::ast::{Container, Ctx, Derive};
+use crate::ast::{Container, Ctx, Data, Derive, Field, Managed, Policy};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::DeriveInput;
-mod convert;
+mod bulk;
+pub(crate) mod convert;
 mod delete;
+mod entity;
 mod find;
 mod insert;
 mod persist;
+mod relation;
+mod schema;
 mod update;
 
 pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
@@ -17,23 +21,408 @@ pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
         None => return Err(ctx.check().unwrap_err()),
     };
 
-    let persist = persist::gen(&ctx, &cont);
-    let find = find::gen(&ctx, &cont);
-    let extend = convert::gen(&ctx, &cont);
+    for name in cont.attrs.identifiers() {
+        match field(&cont, name) {
+            None => {
+                let msg = format!(
+                    "no field `{}` to identify nodes with, set one with #[neo4j(identifier = \"...\")]",
+                    name
+                );
+                ctx.error_spanned_by(cont.ident(), msg);
+            }
+            Some(field) if field.attrs.policy() != Policy::Always => {
+                let msg =
+                    "identifier fields are always written, they can't be on_create or on_match";
+                ctx.error_spanned_by(field.original, msg);
+            }
+            Some(field) if field.attrs.managed().is_some() => {
+                let msg = "identifier fields can't be versions or timestamps";
+                ctx.error_spanned_by(field.original, msg);
+            }
+            Some(_) => {}
+        }
+    }
+
+    let Data::Struct(_, fields) = &cont.data;
+    for (kind, attr) in &[
+        (Managed::Version, "version"),
+        (Managed::CreatedAt, "created_at"),
+        (Managed::UpdatedAt, "updated_at"),
+    ] {
+        let duplicates = fields
+            .iter()
+            .filter(|field| field.attrs.managed() == Some(*kind))
+            .skip(1);
+        for field in duplicates {
+            let msg = format!("only one field can be #[neo4j({})]", attr);
+            ctx.error_spanned_by(field.original, msg);
+        }
+    }
+
+    // Structs borrowing their fields can't be read from the database, they only write nodes
+    let readers = if cont.is_borrowed() {
+        for field in fields
+            .iter()
+            .filter(|field| field.attrs.relation().is_some())
+        {
+            let msg = "relation fields are loaded from the database, they can't be on structs borrowing their fields";
+            ctx.error_spanned_by(field.original, msg);
+        }
+        vec![]
+    } else {
+        vec![
+            persist::gen(&ctx, &cont),
+            find::gen(&ctx, &cont),
+            convert::gen(&ctx, &cont),
+            entity::gen(&ctx, &cont),
+            relation::gen(&ctx, &cont),
+        ]
+    };
     let update = update::gen(&ctx, &cont);
     let insert = insert::gen(&ctx, &cont);
     let delete = delete::gen(&ctx, &cont);
+    let bulk = bulk::gen(&ctx, &cont);
+    let schema = schema::gen(&ctx, &cont);
 
     ctx.check()?;
 
     let expanded = quote! {
         #insert
         #update
-        #persist
-        #find
-        #extend
         #delete
+        #bulk
+        #schema
+        #(#readers)*
     };
 
     Ok(expanded.into())
 }
+
+/// The field with the given ident, unless skipped
+fn field<'a>(cont: &'a Container, name: &str) -> Option<&'a Field<'a>> {
+    let Data::Struct(_, fields) = &cont.data;
+    fields
+        .iter()
+        .find(|field| field.attrs.is_property() && field.member_name() == name)
+}
+
+/// The field holding the version or the timestamp
+fn managed<'a>(cont: &'a Container, kind: Managed) -> Option<&'a Field<'a>> {
+    let Data::Struct(_, fields) = &cont.data;
+    fields
+        .iter()
+        .find(|field| field.attrs.managed() == Some(kind))
+}
+
+/// The fields named by `#[neo4j(identifier = ...)]`, `id` by default
+fn identifiers<'a>(cont: &'a Container) -> Vec<&'a Field<'a>> {
+    cont.attrs
+        .identifiers()
+        .iter()
+        .filter_map(|name| field(cont, name))
+        .collect()
+}
+
+fn is_identifier(cont: &Container, field: &Field) -> bool {
+    identifiers(cont)
+        .iter()
+        .any(|identifier| std::ptr::eq(*identifier, field))
+}
+
+/// Properties matching the node, e.g. `{org: $org, email: $email}`, along with the calls adding
+/// their parameters to a query
+fn key(cont: &Container) -> Option<(String, Vec<TokenStream>)> {
+    let identifiers = identifiers(cont);
+    if identifiers.len() != cont.attrs.identifiers().len() {
+        return None;
+    }
+
+    let (properties, params): (Vec<String>, Vec<TokenStream>) = identifiers
+        .into_iter()
+        .map(|field| {
+            let param = param(field);
+            let value = value(field);
+            let property = format!("{}: ${}", escape(field.attrs.name()), param);
+            (property, quote!(.param(#param, #value)))
+        })
+        .unzip();
+
+    Some((format!("{{{}}}", properties.join(", ")), params))
+}
+
+/// The value of the field as a `BoltType`, converted with `serialize_with` if any
+pub(crate) fn value(field: &Field) -> TokenStream {
+    let member = &field.member;
+    match field.attrs.serialize_with() {
+        None => quote!(std::convert::Into::<neo4jrs::types::BoltType>::into(self.#member.clone())),
+        Some(serialize) if field.is_optional() => quote! {
+            match &self.#member {
+                Some(value) => #serialize(value),
+                None => neo4jrs::types::BoltType::Null(neo4jrs::types::BoltNull),
+            }
+        },
+        Some(serialize) => quote!(#serialize(&self.#member)),
+    }
+}
+
+/// The properties of the struct held by a `#[neo4j(flatten)]` field as a `BoltMap`
+pub(crate) fn flattened(field: &Field) -> TokenStream {
+    let member = &field.member;
+    let ty = field.ty;
+    quote!(<#ty as neo4jrs::Properties>::properties(&self.#member))
+}
+
+/// The `#[neo4j(flatten)]` fields
+pub(crate) fn flattened_fields<'a>(cont: &'a Container) -> Vec<&'a Field<'a>> {
+    let Data::Struct(_, fields) = &cont.data;
+    fields
+        .iter()
+        .filter(|field| field.attrs.is_flattened())
+        .collect()
+}
+
+/// The condition keeping the nodes which aren't soft deleted, e.g. `n.deleted_at is null`
+fn not_deleted(cont: &Container) -> Option<String> {
+    cont.attrs
+        .soft_delete()
+        .map(|property| format!("n.{} is null", escape(property)))
+}
+
+/// Name of the query parameter holding the field value, the field ident, or its index in a tuple
+/// struct
+fn param(field: &Field) -> String {
+    field.member_name()
+}
+
+/// Label pattern of the node, e.g. `:Person:Employee`
+fn labels(cont: &Container) -> String {
+    cont.attrs
+        .labels()
+        .iter()
+        .map(|label| format!(":{}", escape(label)))
+        .collect()
+}
+
+/// Quotes a label or property name with backticks. Names are always quoted: telling plain
+/// identifiers apart takes the reserved words of cypher, which `neo4jrs::escape_identifier` owns
+fn escape(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// Runs `f` with the container of the struct, for the tests of the generators
+#[cfg(test)]
+fn with_container<T>(input: DeriveInput, f: impl FnOnce(&Container) -> T) -> T {
+    let ctx = Ctx::new();
+    let cont = Container::from_ast(&ctx, &input, Derive::Label).unwrap();
+    let value = f(&cont);
+    ctx.check().unwrap();
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_errors(input: DeriveInput) -> Vec<String> {
+        expand(input)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn should_match_nodes_by_their_labels_and_identifiers() {
+        let pattern = with_container(
+            parse_quote! {
+                struct Person {
+                    id: String,
+                }
+            },
+            |cont| (labels(cont), key(cont).unwrap().0),
+        );
+        assert_eq!(pattern, (":`Person`".to_owned(), "{`id`: $id}".to_owned()));
+
+        let pattern = with_container(
+            parse_quote! {
+                #[neo4j(labels = ["Person", "Team Member"], identifier = ["org", "email"], rename_all = "camelCase")]
+                struct Person {
+                    org: String,
+                    #[neo4j(rename = "e-mail")]
+                    email: String,
+                }
+            },
+            |cont| (labels(cont), key(cont).unwrap().0),
+        );
+        assert_eq!(
+            pattern,
+            (
+                ":`Person`:`Team Member`".to_owned(),
+                "{`org`: $org, `e-mail`: $email}".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn should_always_quote_names() {
+        assert_eq!(escape("name"), "`name`");
+        assert_eq!(escape("first name"), "`first name`");
+        assert_eq!(escape("a`b"), "`a``b`");
+    }
+
+    #[test]
+    fn should_refuse_empty_names() {
+        let errors = expand_errors(parse_quote! {
+            #[neo4j(labels = ["Order", ""])]
+            struct Order {
+                id: String,
+                #[neo4j(rename = "")]
+                note: String,
+            }
+        });
+        assert_eq!(
+            errors,
+            vec![
+                "labels, relationship types and properties can't be empty nor contain the null character",
+                "properties can't be empty nor contain the null character",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_load_relations_on_structs_borrowing_their_fields() {
+        let errors = expand_errors(parse_quote! {
+            struct PersonView<'a> {
+                id: &'a str,
+                #[neo4j(relation = "KNOWS")]
+                friends: Vec<Person>,
+            }
+        });
+        assert_eq!(
+            errors,
+            vec!["relation fields are loaded from the database, they can't be on structs borrowing their fields"]
+        );
+    }
+
+    #[test]
+    fn should_report_invalid_attributes() {
+        let errors = expand_errors(parse_quote! {
+            #[neo4j(label = "Person", labels = ["Employee"], rename_all = "camel", chunk_size = 0)]
+            struct Person {
+                id: String,
+            }
+        });
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0], "duplicate neo4j attribute `labels`");
+        assert!(errors[1].starts_with("unknown rename rule `rename_all = \"camel\"`"));
+        assert_eq!(errors[2], "expected a positive chunk size");
+
+        let errors = expand_errors(parse_quote! {
+            #[neo4j(from = Person, to = Person, soft_delete = 1)]
+            struct Person {
+                id: String,
+            }
+        });
+        assert_eq!(
+            errors,
+            vec![
+                "neo4j attribute `from` is not supported by #[derive(Label)]",
+                "neo4j attribute `to` is not supported by #[derive(Label)]",
+                "expected the property as a string",
+            ]
+        );
+
+        let errors = expand_errors(parse_quote! {
+            struct Person {
+                name: String,
+            }
+        });
+        assert_eq!(
+            errors,
+            vec![
+                "no field `id` to identify nodes with, set one with #[neo4j(identifier = \"...\")]"
+            ]
+        );
+
+        let errors = expand_errors(parse_quote! {
+            #[neo4j(type = "PERSON", from = Person)]
+            struct Person {
+                id: String,
+            }
+        });
+        assert_eq!(
+            errors,
+            vec![
+                "neo4j attribute `type` is not supported by #[derive(Label)]",
+                "neo4j attribute `from` is not supported by #[derive(Label)]",
+            ]
+        );
+
+        let errors = expand_errors(parse_quote! {
+            struct Person {
+                id: String,
+                #[neo4j(relation = "WORKS_AT", on_create)]
+                employer: Option<Company>,
+                #[neo4j(relation = "KNOWS")]
+                friends: HashSet<Person>,
+                #[neo4j(direction = "out")]
+                name: String,
+            }
+        });
+        assert_eq!(
+            errors,
+            vec![
+                "relation fields are synced on every persist, they can't be on_create or on_match",
+                "#[neo4j(direction)] requires #[neo4j(relation)]",
+                "relation fields hold the related nodes in an Option or a Vec",
+            ]
+        );
+
+        let errors = expand_errors(parse_quote! {
+            struct Person {
+                id: String,
+                #[neo4j(skip, unique)]
+                cache: String,
+            }
+        });
+        assert_eq!(
+            errors,
+            vec!["constraints and indexes are only supported on properties"]
+        );
+
+        let errors = expand_errors(parse_quote! {
+            struct Person {
+                id: String,
+                #[neo4j(flatten, with = "codec")]
+                address: Address,
+            }
+        });
+        assert_eq!(
+            errors,
+            vec!["#[neo4j(flatten)] can't be combined with relation, on_create, on_match, constraints, indexes or converters"]
+        );
+
+        let errors = expand_errors(parse_quote! {
+            struct Document {
+                #[neo4j(version)]
+                id: i64,
+                #[neo4j(version)]
+                version: i64,
+                #[neo4j(created_at, updated_at)]
+                stamp: String,
+                #[neo4j(updated_at, on_match)]
+                updated_at: String,
+            }
+        });
+        assert_eq!(
+            errors,
+            vec![
+                "a field can only be one of #[neo4j(version)], #[neo4j(created_at)] or #[neo4j(updated_at)]",
+                "versions and timestamps are written by the generated queries, they can't be skip, flatten, relation, on_create or on_match",
+                "identifier fields can't be versions or timestamps",
+                "only one field can be #[neo4j(version)]",
+            ]
+        );
+    }
+}
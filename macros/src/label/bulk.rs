@@ -0,0 +1,242 @@
+//! Inject functions writing many structs with one query per chunk, using `UNWIND`.
+use super::{escape, flattened, flattened_fields, is_identifier, key, labels, value};
+use crate::ast::{iter_fields, Container, Ctx, Managed, Policy};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn gen(_cx: &Ctx, cont: &Container) -> TokenStream {
+    if key(cont).is_none() {
+        return TokenStream::new();
+    }
+    let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let chunk_size = cont.attrs.chunk_size();
+    let Bulk {
+        insert_query,
+        upsert_query,
+        properties,
+        keys,
+        on_create,
+        on_match,
+        props,
+    } = bulk(cont);
+    let flattened = flattened_fields(cont)
+        .into_iter()
+        .map(flattened)
+        .collect::<Vec<_>>();
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Creates a node for each struct, returning the number of nodes created
+            pub async fn insert_many(rows: &[Self], graph: &impl neo4jrs::Execute) -> neo4jrs::Result<usize> {
+                Self::write_many(#insert_query, rows, Self::bulk_insert_row, graph).await
+            }
+
+            /// Same as `upsert` for each struct, returning the number of nodes created or updated
+            pub async fn upsert_many(rows: &[Self], graph: &impl neo4jrs::Execute) -> neo4jrs::Result<usize> {
+                Self::write_many(#upsert_query, rows, Self::bulk_upsert_row, graph).await
+            }
+
+            async fn write_many(
+                query: &str,
+                rows: &[Self],
+                to_row: fn(&Self) -> neo4jrs::types::BoltType,
+                graph: &impl neo4jrs::Execute,
+            ) -> neo4jrs::Result<usize> {
+                let mut count = 0;
+                for chunk in rows.chunks(#chunk_size) {
+                    let rows = chunk.iter().map(to_row).collect::<Vec<_>>();
+                    let query = neo4jrs::Query::new(query).param("rows", neo4jrs::types::BoltList::from(rows));
+                    let mut result = graph.execute(query).await?;
+                    if let Some(row) = result.next().await? {
+                        count += row.get::<i64>("count").unwrap_or(0) as usize;
+                    }
+                }
+                Ok(count)
+            }
+
+            fn bulk_insert_row(&self) -> neo4jrs::types::BoltType {
+                let properties: neo4jrs::types::BoltMap = vec![#(#properties),*].into_iter()#(.chain(#flattened))*.collect();
+                properties.into()
+            }
+
+            fn bulk_upsert_row(&self) -> neo4jrs::types::BoltType {
+                let key: neo4jrs::types::BoltMap = vec![#(#keys),*].into_iter().collect();
+                let on_create: neo4jrs::types::BoltMap = vec![#(#on_create),*].into_iter().collect();
+                let on_match: neo4jrs::types::BoltMap = vec![#(#on_match),*].into_iter().collect();
+                let props: neo4jrs::types::BoltMap = vec![#(#props),*].into_iter()#(.chain(#flattened))*.collect();
+                let mut row = neo4jrs::types::BoltMap::default();
+                row.put("key".into(), key.into());
+                row.put("on_create".into(), on_create.into());
+                row.put("on_match".into(), on_match.into());
+                row.put("props".into(), props.into());
+                row.into()
+            }
+        }
+    }
+}
+
+/// The queries of `insert_many` and `upsert_many`, along with the entries of the maps sent for
+/// each struct: its properties for `insert_many`, split by how they are written for
+/// `upsert_many`
+struct Bulk {
+    insert_query: String,
+    upsert_query: String,
+    properties: Vec<TokenStream>,
+    keys: Vec<TokenStream>,
+    on_create: Vec<TokenStream>,
+    on_match: Vec<TokenStream>,
+    props: Vec<TokenStream>,
+}
+
+fn bulk(cont: &Container) -> Bulk {
+    let labels = labels(cont);
+    let mut properties = vec![];
+    let mut keys = vec![];
+    let mut matching = vec![];
+    let mut on_create = vec![];
+    let mut on_match = vec![];
+    let mut props = vec![];
+    let mut created = vec![];
+    let mut matched = vec![];
+    let mut stamps = vec![];
+    iter_fields(cont, |field| {
+        let value = value(field);
+        let property = field.attrs.name();
+        let escaped = escape(property);
+        match field.attrs.managed() {
+            Some(Managed::CreatedAt) => {
+                created.push(format!("n.{} = datetime()", escaped));
+                return Some(());
+            }
+            Some(Managed::UpdatedAt) => {
+                stamps.push(format!("n.{} = datetime()", escaped));
+                return Some(());
+            }
+            Some(Managed::Version) => matched.push(format!("n.{} = n.{} + 1", escaped, escaped)),
+            None => {}
+        }
+        let entry = quote!((neo4jrs::types::BoltString::from(#property), #value));
+        properties.push(entry.clone());
+        if field.attrs.managed() == Some(Managed::Version) {
+            on_create.push(entry);
+        } else if is_identifier(cont, field) {
+            keys.push(entry);
+            matching.push(format!("{}: row.key.{}", escaped, escaped));
+        } else {
+            match field.attrs.policy() {
+                Policy::Always => props.push(entry),
+                Policy::OnCreate => on_create.push(entry),
+                Policy::OnMatch => on_match.push(entry),
+            }
+        }
+        Some(())
+    });
+
+    let insert_sets = std::iter::once("n = row".to_owned())
+        .chain(created.iter().cloned())
+        .chain(stamps.iter().cloned())
+        .collect::<Vec<_>>();
+    let insert_query = format!(
+        "unwind $rows as row create (n{}) set {} return count(n) as count",
+        labels,
+        insert_sets.join(", ")
+    );
+    let mut upsert_query = format!(
+        "unwind $rows as row merge (n{} {{{}}})",
+        labels,
+        matching.join(", ")
+    );
+    if !on_create.is_empty() {
+        created.insert(0, "n += row.on_create".to_owned());
+    }
+    if !created.is_empty() {
+        upsert_query.push_str(&format!(" on create set {}", created.join(", ")));
+    }
+    if !on_match.is_empty() {
+        matched.insert(0, "n += row.on_match".to_owned());
+    }
+    if !matched.is_empty() {
+        upsert_query.push_str(&format!(" on match set {}", matched.join(", ")));
+    }
+    if !props.is_empty() || !flattened_fields(cont).is_empty() {
+        stamps.insert(0, "n += row.props".to_owned());
+    }
+    if !stamps.is_empty() {
+        upsert_query.push_str(&format!(" set {}", stamps.join(", ")));
+    }
+    upsert_query.push_str(" return count(n) as count");
+
+    Bulk {
+        insert_query,
+        upsert_query,
+        properties,
+        keys,
+        on_create,
+        on_match,
+        props,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label::with_container;
+    use syn::parse_quote;
+
+    fn queries(input: syn::DeriveInput) -> (String, String) {
+        with_container(input, |cont| {
+            let bulk = bulk(cont);
+            (bulk.insert_query, bulk.upsert_query)
+        })
+    }
+
+    #[test]
+    fn should_write_many_with_unwind() {
+        let queries = queries(parse_quote! {
+            #[neo4j(identifier = ["org", "email"], chunk_size = 250)]
+            struct User {
+                org: String,
+                #[neo4j(rename = "e-mail")]
+                email: String,
+                name: String,
+                #[neo4j(on_create)]
+                created_at: i64,
+            }
+        });
+
+        assert_eq!(
+            queries,
+            (
+                "unwind $rows as row create (n:`User`) set n = row return count(n) as count".to_owned(),
+                "unwind $rows as row merge (n:`User` {`org`: row.key.`org`, `e-mail`: row.key.`e-mail`}) on create set n += row.on_create set n += row.props return count(n) as count".to_owned(),
+            )
+        );
+    }
+
+    #[test]
+    fn should_increment_the_version_and_stamp_timestamps() {
+        let queries = queries(parse_quote! {
+            #[neo4j(rename_all = "camelCase")]
+            struct Document {
+                id: i64,
+                body: String,
+                #[neo4j(version)]
+                version: i64,
+                #[neo4j(created_at)]
+                created_at: Option<String>,
+                #[neo4j(updated_at)]
+                updated_at: Option<String>,
+            }
+        });
+
+        assert_eq!(
+            queries,
+            (
+                "unwind $rows as row create (n:`Document`) set n = row, n.`createdAt` = datetime(), n.`updatedAt` = datetime() return count(n) as count".to_owned(),
+                "unwind $rows as row merge (n:`Document` {`id`: row.key.`id`}) on create set n += row.on_create, n.`createdAt` = datetime() on match set n.`version` = n.`version` + 1 set n += row.props, n.`updatedAt` = datetime() return count(n) as count".to_owned(),
+            )
+        );
+    }
+}
@@ -1,38 +1,116 @@
 //! implement conversion types
-use crate::ast::{iter_fields, Container, Ctx, Default};
+use crate::ast::{Container, Ctx, Data, Default};
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 
-pub fn gen(cx: &Ctx, cont: &Container) -> TokenStream {
+pub fn gen(_cx: &Ctx, cont: &Container) -> TokenStream {
     let name = cont.ident();
-    let injections = iter_fields(cx, cont, |field| {
-        let fname = field.attrs.name();
-        let ident = field.original.ident.as_ref()?;
-        let is_optional = field.is_optional();
-        Some(match field.attrs.default() {
-            Default::None => {
-                if is_optional {
-                    quote!(#ident: n.get(#fname),)
-                } else {
-                    quote!(#ident: n.get(#fname).unwrap(),)
-                }
+    let injections = injections(cont, Source::Entity);
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics std::convert::TryFrom<neo4jrs::Node> for #name #ty_generics #where_clause {
+            type Error = neo4jrs::Error;
+
+            fn try_from(n: neo4jrs::Node) -> neo4jrs::Result<Self> {
+                Ok(Self { #(#injections)* })
             }
-            Default::Default(or_value) | Default::Custom(or_value) => {
-                if is_optional {
-                    quote!(#ident: Some(n.get(#fname).unwrap_or(#or_value)),)
-                } else {
-                    quote!(#ident: n.get(#fname).unwrap_or(#or_value),)
-                }
+        }
+
+        impl #impl_generics std::convert::TryFrom<neo4jrs::Row> for #name #ty_generics #where_clause {
+            type Error = neo4jrs::Error;
+
+            fn try_from(n: neo4jrs::Row) -> neo4jrs::Result<Self> {
+                Ok(Self { #(#injections)* })
             }
-        })
-    });
+        }
+    }
+}
 
-    // TODO: convert to bolt type instead
-    let expanded = quote! {
-        impl From<neo4jrs::Node> for #name {
-            fn from(n: neo4jrs::Node) -> Self { Self { #(#injections)* } }
+/// Where the properties are read from
+#[derive(Copy, Clone)]
+pub(crate) enum Source {
+    /// `n`, any value with a `get::<BoltType>(&str)` method
+    Entity,
+    /// `get`, the `&dyn Fn(&str) -> Option<BoltType>` of `Properties::from_properties`
+    Properties,
+}
+
+/// The initializers of the fields, reading the properties from the source
+pub(crate) fn injections(cont: &Container, source: Source) -> Vec<TokenStream> {
+    let target = cont.ident().to_string();
+    let Data::Struct(_, fields) = &cont.data;
+
+    let injections = fields.iter().map(|field| {
+        let member = &field.member;
+        let default = match field.attrs.default() {
+            Default::None => None,
+            Default::Default(or_value) | Default::Custom(or_value) => Some(or_value),
+        };
+
+        if field.attrs.is_flattened() {
+            let ty = field.ty;
+            let get = match source {
+                Source::Entity => quote!(&|key: &str| n.get::<neo4jrs::types::BoltType>(key)),
+                Source::Properties => quote!(get),
+            };
+            return quote!(#member: <#ty as neo4jrs::Properties>::from_properties(#get)?,);
+        }
+
+        if !field.attrs.is_property() {
+            let default = default
+                .cloned()
+                .unwrap_or_else(|| quote!(std::default::Default::default()));
+            return quote!(#member: #default,);
         }
-    };
 
-    expanded.into()
+        let fname = field.attrs.name();
+        let field_name = field.member_name();
+        let expected = field
+            .ty_str
+            .clone()
+            .unwrap_or_else(|| field.ty.to_token_stream().to_string());
+        let deserialize = match field.attrs.deserialize_with() {
+            Some(deserialize) => quote!(#deserialize(value)),
+            None => quote!(std::convert::TryFrom::try_from(value)),
+        };
+        let convert = quote! {
+            #deserialize.map_err(|_| neo4jrs::Error::InvalidProperty {
+                target: #target.to_owned(),
+                field: #field_name.to_owned(),
+                property: #fname.to_owned(),
+                expected: #expected.to_owned(),
+            })?
+        };
+        let missing = match (field.is_optional(), default) {
+            (true, Some(or_value)) => quote!(Some(#or_value)),
+            (true, None) => quote!(None),
+            (false, Some(or_value)) => quote!(#or_value),
+            (false, None) => quote! {
+                return Err(neo4jrs::Error::MissingProperty {
+                    target: #target.to_owned(),
+                    field: #field_name.to_owned(),
+                property: #fname.to_owned(),
+                })
+            },
+        };
+        let get = match source {
+            Source::Entity => quote!(n.get::<neo4jrs::types::BoltType>(#fname)),
+            Source::Properties => quote!(get(#fname)),
+        };
+        let present = if field.is_optional() {
+            quote!(Some(#convert))
+        } else {
+            convert
+        };
+
+        quote! {
+            #member: match #get {
+                None | Some(neo4jrs::types::BoltType::Null(_)) => #missing,
+                Some(value) => #present,
+            },
+        }
+    });
+    injections.collect()
 }
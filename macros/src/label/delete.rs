@@ -1,26 +1,236 @@
-use crate::ast::{Container, Ctx};
+//! Inject functions deleting nodes, or marking them as deleted with `#[neo4j(soft_delete)]`.
+use super::{escape, key, labels, not_deleted, param};
+use crate::ast::{iter_fields, Container, Ctx, Field};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
-/// Inject pub update function to insert struct to graph db
 pub fn gen(_cx: &Ctx, cont: &Container) -> TokenStream {
-    let identifier = cont.attrs.identifier();
-    let identifier_ident = format_ident!("{}", identifier);
+    let (key, params) = match key(cont) {
+        Some(key) => key,
+        None => return TokenStream::new(),
+    };
     let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let (doc, doc_where) = match (cont.attrs.soft_delete(), cont.attrs.detach_delete()) {
+        (Some(_), _) => (
+            "Marks the node as deleted, hiding it from the finders, returning the number of nodes marked",
+            "Marks the nodes where the property equals the value as deleted, returning the number of nodes marked",
+        ),
+        (None, true) => (
+            "Deletes the node along with its relationships, returning the number of nodes deleted",
+            "Deletes the nodes where the property equals the value along with their relationships, returning the number of nodes deleted",
+        ),
+        (None, false) => (
+            "Deletes the node, which must have no relationships, returning the number of nodes deleted",
+            "Deletes the nodes where the property equals the value, which must have no relationships, returning the number of nodes deleted",
+        ),
+    };
+
+    let delete_query = delete_query(cont, &key);
 
-    let delete_query = format!(
-        "match (n:{name} {{{}: ${}}}) delete n",
-        identifier, identifier,
-    );
+    let delete_where = iter_fields(cont, |field| {
+        if !has_delete_where(cont, field) {
+            return None;
+        }
+        let param = param(field);
+        let method = format_ident!("delete_where_{}", param);
+        let ty = field.value_ty();
+        let query = delete_where_query(cont, field);
+        let value = match field.attrs.serialize_with() {
+            Some(serialize) => quote!(#serialize(&value)),
+            None => quote!(std::convert::Into::<neo4jrs::types::BoltType>::into(value)),
+        };
+
+        Some(quote! {
+            #[doc = #doc_where]
+            pub async fn #method(value: impl Into<#ty>, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<usize> {
+                let value: #ty = value.into();
+                let query = neo4jrs::Query::new(#query).param("value", #value);
+                Self::count_deleted(query, graph).await
+            }
+        })
+    });
 
-    let expanded = quote! {
-        impl #name {
-            pub async fn delete(&self, graph: &impl neo4jrs::Execute) -> Result<(), neo4jrs::Error> {
-                let query = neo4jrs::Query::new(#delete_query).param(#identifier, self.#identifier_ident.clone());
-                graph.run(query).await
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #doc]
+            pub async fn delete(&self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<usize> {
+                let query = neo4jrs::Query::new(#delete_query) #(#params)*;
+                Self::count_deleted(query, graph).await
+            }
+
+            #(#delete_where)*
+
+            async fn count_deleted(query: neo4jrs::Query, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<usize> {
+                match graph.execute(query).await?.next().await? {
+                    Some(row) => Ok(row.get::<i64>("count").unwrap_or(0) as usize),
+                    None => Ok(0),
+                }
             }
         }
-    };
+    }
+}
+
+/// What is done to the matched nodes, returning how many there were as `count`
+fn action(cont: &Container) -> String {
+    match cont.attrs.soft_delete() {
+        Some(property) => format!(
+            "set n.{} = datetime() return count(n) as count",
+            escape(property)
+        ),
+        None if cont.attrs.detach_delete() => "detach delete n return count(*) as count".to_owned(),
+        None => "delete n return count(*) as count".to_owned(),
+    }
+}
+
+/// The query deleting the node matched by its key, e.g. `{id: $id}`
+fn delete_query(cont: &Container, key: &str) -> String {
+    match not_deleted(cont) {
+        Some(condition) => format!(
+            "match (n{} {}) where {} {}",
+            labels(cont),
+            key,
+            condition,
+            action(cont)
+        ),
+        None => format!("match (n{} {}) {}", labels(cont), key, action(cont)),
+    }
+}
+
+/// Whether the field gets a `delete_where_<field>` method, versions, timestamps and the soft
+/// delete marker are written by the database
+fn has_delete_where(cont: &Container, field: &Field) -> bool {
+    field.attrs.managed().is_none() && cont.attrs.soft_delete() != Some(field.attrs.name())
+}
+
+/// The query of `delete_where_<field>`, deleting the nodes where the property equals `$value`
+fn delete_where_query(cont: &Container, field: &Field) -> String {
+    let mut conditions = vec![format!("n.{} = $value", escape(field.attrs.name()))];
+    conditions.extend(not_deleted(cont));
+    format!(
+        "match (n{}) where {} {}",
+        labels(cont),
+        conditions.join(" and "),
+        action(cont)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label::{field, with_container};
+    use syn::parse_quote;
+
+    #[test]
+    fn should_delete_nodes_with_or_without_their_relationships() {
+        let queries = with_container(
+            parse_quote! {
+                #[neo4j(labels = ["Person", "Employee"], identifier = ["org", "email"])]
+                struct Person {
+                    org: String,
+                    email: String,
+                }
+            },
+            |cont| {
+                (
+                    delete_query(cont, &key(cont).unwrap().0),
+                    delete_where_query(cont, field(cont, "email").unwrap()),
+                )
+            },
+        );
+        assert_eq!(
+            queries,
+            (
+                "match (n:`Person`:`Employee` {`org`: $org, `email`: $email}) delete n return count(*) as count".to_owned(),
+                "match (n:`Person`:`Employee`) where n.`email` = $value delete n return count(*) as count".to_owned(),
+            )
+        );
+
+        let queries = with_container(
+            parse_quote! {
+                #[neo4j(detach_delete)]
+                struct Tag {
+                    id: String,
+                    #[neo4j(rename = "tag name")]
+                    name: Option<String>,
+                }
+            },
+            |cont| {
+                (
+                    delete_query(cont, &key(cont).unwrap().0),
+                    delete_where_query(cont, field(cont, "name").unwrap()),
+                )
+            },
+        );
+        assert_eq!(
+            queries,
+            (
+                "match (n:`Tag` {`id`: $id}) detach delete n return count(*) as count".to_owned(),
+                "match (n:`Tag`) where n.`tag name` = $value detach delete n return count(*) as count".to_owned(),
+            )
+        );
+    }
+
+    #[test]
+    fn should_mark_soft_deleted_nodes() {
+        let queries = with_container(
+            parse_quote! {
+                #[neo4j(soft_delete)]
+                struct Tag {
+                    id: String,
+                }
+            },
+            |cont| {
+                (
+                    delete_query(cont, &key(cont).unwrap().0),
+                    delete_where_query(cont, field(cont, "id").unwrap()),
+                )
+            },
+        );
+
+        assert_eq!(
+            queries,
+            (
+                "match (n:`Tag` {`id`: $id}) where n.`deleted_at` is null set n.`deleted_at` = datetime() return count(n) as count".to_owned(),
+                "match (n:`Tag`) where n.`id` = $value and n.`deleted_at` is null set n.`deleted_at` = datetime() return count(n) as count".to_owned(),
+            )
+        );
+    }
+
+    #[test]
+    fn should_not_delete_where_the_database_writes_the_property() {
+        let fields = with_container(
+            parse_quote! {
+                #[neo4j(soft_delete = "deletedAt")]
+                struct Document {
+                    id: i64,
+                    #[neo4j(version)]
+                    version: i64,
+                    #[neo4j(updated_at)]
+                    updated_at: Option<DateTime<FixedOffset>>,
+                    #[neo4j(rename = "deletedAt")]
+                    deleted_at: Option<DateTime<FixedOffset>>,
+                    title: String,
+                }
+            },
+            |cont| {
+                iter_fields(cont, |field| {
+                    Some((field.member_name(), has_delete_where(cont, field)))
+                })
+            },
+        );
 
-    expanded.into()
+        assert_eq!(
+            fields,
+            vec![
+                ("id".to_owned(), true),
+                ("version".to_owned(), false),
+                ("updated_at".to_owned(), false),
+                ("deleted_at".to_owned(), false),
+                ("title".to_owned(), true),
+            ]
+        );
+    }
 }
@@ -0,0 +1,35 @@
+//! Implement `neo4jrs::NodeEntity`, used by the relationships to match their nodes.
+use super::{identifiers, value};
+use crate::ast::{Container, Ctx};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn gen(_cx: &Ctx, cont: &Container) -> TokenStream {
+    if super::key(cont).is_none() {
+        return TokenStream::new();
+    }
+    let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let labels = cont.attrs.labels();
+    let soft_delete = cont
+        .attrs
+        .soft_delete()
+        .map(|property| quote!(const SOFT_DELETE: Option<&'static str> = Some(#property);));
+    let entries = identifiers(cont).into_iter().map(|field| {
+        let value = value(field);
+        let property = field.attrs.name();
+        quote!((neo4jrs::types::BoltString::from(#property), #value))
+    });
+
+    quote! {
+        impl #impl_generics neo4jrs::NodeEntity for #name #ty_generics #where_clause {
+            const LABELS: &'static [&'static str] = &[#(#labels),*];
+            #soft_delete
+
+            fn key(&self) -> neo4jrs::types::BoltMap {
+                vec![#(#entries),*].into_iter().collect()
+            }
+        }
+    }
+}
@@ -1,55 +1,58 @@
-use crate::ast::{iter_fields, Container, Ctx};
+use super::{escape, labels, not_deleted, param, relation};
+use crate::ast::{iter_fields, Container, Ctx, Field};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
-pub fn gen(cx: &Ctx, cont: &Container) -> TokenStream {
+pub fn gen(_cx: &Ctx, cont: &Container) -> TokenStream {
     let name = cont.ident();
-    let get_all_query = format!("match (n:{name}) return n");
-    let find_fns = iter_fields(cx, cont, |field| {
-        let fname = field.attrs.name();
-        let by_many_ident = format_ident!("find_many_by_{fname}");
-        let by_one_ident = format_ident!("find_one_by_{fname}");
-        let query = format!("match (n:{name} {{{fname}: ${fname}}}) return n");
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let get_all_query = get_all_query(cont);
+    let find_fns = iter_fields(cont, |field| {
+        let param = param(field);
+        let by_many_ident = format_ident!("find_many_by_{param}");
+        let by_one_ident = format_ident!("find_one_by_{param}");
+        let query = find_by_query(cont, field);
 
         Some(quote! {
-            pub async fn #by_many_ident<T: Into<neo4jrs::types::BoltType>>(val: T, graph: &impl neo4jrs::Execute) -> Result<Vec<Self>, neo4jrs::Error> {
-                let query = neo4jrs::Query::new(#query).param(#fname, val);
+            pub async fn #by_many_ident(val: impl Into<neo4jrs::types::BoltType>, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Vec<Self>> {
+                let query = neo4jrs::Query::new(#query).param(#param, val);
                 Self::query(query, graph).await
             }
 
-            pub async fn #by_one_ident<T: Into<neo4jrs::types::BoltType>>(val: T, graph: &impl neo4jrs::Execute) -> Result<Self, neo4jrs::Error> {
-                let query = neo4jrs::Query::new(#query).param(#fname, val);
+            pub async fn #by_one_ident(val: impl Into<neo4jrs::types::BoltType>, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Self> {
+                let query = neo4jrs::Query::new(#query).param(#param, val);
                 Self::query_one(query, graph).await
             }
         })
     });
+    let finder = finder(cont);
 
     let expanded = quote! {
 
-        impl #name {
+        impl #impl_generics #name #ty_generics #where_clause {
 
-            pub async fn get_all(graph: &impl neo4jrs::Execute) -> Result<Vec<Self>, neo4jrs::Error> {
-                println!(#get_all_query);
+            pub async fn get_all(graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Vec<Self>> {
                 Self::query(neo4jrs::Query::new(#get_all_query), graph).await
             }
 
-            pub async fn query(query: neo4jrs::Query, graph: &impl neo4jrs::Execute) -> Result<Vec<Self>, neo4jrs::Error> {
+            pub async fn query(query: neo4jrs::Query, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Vec<Self>> {
                 let mut list = vec![];
                 let mut result = graph.execute(query).await?;
 
-                while let Ok(Some(row)) = result.next().await {
+                while let Some(row) = result.next().await? {
                     if let Some(n) = row.get::<neo4jrs::Node>("n") {
-                        list.push(n.into());
+                        list.push(<Self as std::convert::TryFrom<neo4jrs::Node>>::try_from(n)?);
                     }
                 }
 
                 Ok(list)
             }
 
-            pub async fn query_one(query: neo4jrs::Query, graph: &impl neo4jrs::Execute) -> Result<Self, neo4jrs::Error> {
-                if let Ok(Some(row)) = graph.execute(query).await?.next().await {
+            pub async fn query_one(query: neo4jrs::Query, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Self> {
+                if let Some(row) = graph.execute(query).await?.next().await? {
                     if let Some(n) = row.get::<neo4jrs::Node>("n") {
-                        return Ok(n.into())
+                        return <Self as std::convert::TryFrom<neo4jrs::Node>>::try_from(n)
                     }
                 }
 
@@ -58,7 +61,237 @@ pub fn gen(cx: &Ctx, cont: &Container) -> TokenStream {
 
             #(#find_fns)*
         }
+
+        #finder
     };
 
     expanded.into()
 }
+
+/// The condition leaving out the soft-deleted nodes, if any, e.g. ` where n.deleted_at is null`
+fn condition(cont: &Container) -> String {
+    not_deleted(cont)
+        .map(|condition| format!(" where {condition}"))
+        .unwrap_or_default()
+}
+
+/// The query of `get_all`, e.g. `match (n:Person) return n`
+fn get_all_query(cont: &Container) -> String {
+    format!("match (n{}){} return n", labels(cont), condition(cont))
+}
+
+/// The query of `find_many_by_<field>` and `find_one_by_<field>`, matching the property with the
+/// parameter named after the field
+fn find_by_query(cont: &Container, field: &Field) -> String {
+    format!(
+        "match (n{} {{{}: ${}}}){} return n",
+        labels(cont),
+        escape(field.attrs.name()),
+        param(field),
+        condition(cont)
+    )
+}
+
+/// A typed wrapper of `neo4jrs::Finder`, with conditions and orders for each field
+fn finder(cont: &Container) -> TokenStream {
+    let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_generics = cont.generics;
+    let struct_where = &cont.generics.where_clause;
+    let vis = &cont.original.vis;
+    let finder = format_ident!("{}Finder", name);
+    let labels = cont.attrs.labels();
+    let doc = format!("Finds [`{name}`] nodes, see [`{name}::find`]");
+    let new = match cont.attrs.soft_delete() {
+        Some(property) => {
+            quote!(neo4jrs::Finder::new(&[#(#labels),*]).filter_null(#property, true))
+        }
+        None => quote!(neo4jrs::Finder::new(&[#(#labels),*])),
+    };
+
+    let mut methods = iter_fields(cont, |field| {
+        let param = param(field);
+        let property = field.attrs.name();
+        let ty = field.value_ty();
+        let method = |suffix: &str| format_ident!("where_{}{}", param, suffix);
+        let compare = |suffix: &str, operator: TokenStream| {
+            let method = method(suffix);
+            quote! {
+                pub fn #method(self, value: impl Into<#ty>) -> Self {
+                    let value: #ty = value.into();
+                    self.filter(#property, neo4jrs::Operator::#operator, value)
+                }
+            }
+        };
+
+        let mut methods = vec![
+            compare("", quote!(Eq)),
+            compare("_ne", quote!(Ne)),
+            compare("_gt", quote!(Gt)),
+            compare("_gte", quote!(Gte)),
+            compare("_lt", quote!(Lt)),
+            compare("_lte", quote!(Lte)),
+        ];
+        if field.is_string() {
+            methods.push(compare("_starts_with", quote!(StartsWith)));
+            methods.push(compare("_ends_with", quote!(EndsWith)));
+            methods.push(compare("_contains", quote!(Contains)));
+        }
+
+        let is_in = method("_in");
+        let is_null = method("_is_null");
+        let is_not_null = method("_is_not_null");
+        let order_by = format_ident!("order_by_{}", param);
+        let order_by_desc = format_ident!("order_by_{}_desc", param);
+        methods.push(quote! {
+            pub fn #is_in(self, values: impl IntoIterator<Item = impl Into<#ty>>) -> Self {
+                let values = values
+                    .into_iter()
+                    .map(|value| Into::<neo4jrs::types::BoltType>::into(Into::<#ty>::into(value)))
+                    .collect::<Vec<_>>();
+                self.filter(#property, neo4jrs::Operator::In, values)
+            }
+
+            pub fn #is_null(self) -> Self {
+                Self { inner: self.inner.filter_null(#property, true) }
+            }
+
+            pub fn #is_not_null(self) -> Self {
+                Self { inner: self.inner.filter_null(#property, false) }
+            }
+
+            pub fn #order_by(self) -> Self {
+                Self { inner: self.inner.order_by(#property, neo4jrs::Order::Asc) }
+            }
+
+            pub fn #order_by_desc(self) -> Self {
+                Self { inner: self.inner.order_by(#property, neo4jrs::Order::Desc) }
+            }
+        });
+        Some(quote!(#(#methods)*))
+    });
+    methods.extend(relation::includes(cont));
+
+    quote! {
+        #[doc = #doc]
+        #vis struct #finder #struct_generics #struct_where {
+            inner: neo4jrs::Finder<#name #ty_generics>,
+        }
+
+        impl #impl_generics std::clone::Clone for #finder #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                Self { inner: self.inner.clone() }
+            }
+        }
+
+        impl #impl_generics std::fmt::Debug for #finder #ty_generics #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!(#finder)).field("inner", &self.inner).finish()
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Finds nodes with typed conditions, e.g. `find().where_id(1).first(&graph)`
+            pub fn find() -> #finder #ty_generics {
+                #finder { inner: #new }
+            }
+        }
+
+        impl #impl_generics #finder #ty_generics #where_clause {
+            fn filter(self, property: &str, operator: neo4jrs::Operator, value: impl Into<neo4jrs::types::BoltType>) -> Self {
+                Self { inner: self.inner.filter(property, operator, value) }
+            }
+
+            #(#methods)*
+
+            pub fn skip(self, skip: usize) -> Self {
+                Self { inner: self.inner.skip(skip) }
+            }
+
+            pub fn limit(self, limit: usize) -> Self {
+                Self { inner: self.inner.limit(limit) }
+            }
+
+            pub async fn all(self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Vec<#name #ty_generics>> {
+                self.inner.all(graph).await
+            }
+
+            pub async fn first(self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Option<#name #ty_generics>> {
+                self.inner.first(graph).await
+            }
+
+            pub async fn count(self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<usize> {
+                self.inner.count(graph).await
+            }
+
+            pub async fn exists(self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<bool> {
+                self.inner.exists(graph).await
+            }
+
+            pub fn into_inner(self) -> neo4jrs::Finder<#name #ty_generics> {
+                self.inner
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label::{field, with_container};
+    use syn::parse_quote;
+
+    #[test]
+    fn should_match_nodes_by_their_labels_and_properties() {
+        let queries = with_container(
+            parse_quote! {
+                #[neo4j(label = "Human Being", rename_all = "camelCase")]
+                struct Person {
+                    id: i64,
+                    last_name: String,
+                }
+            },
+            |cont| {
+                (
+                    get_all_query(cont),
+                    find_by_query(cont, field(cont, "last_name").unwrap()),
+                )
+            },
+        );
+
+        assert_eq!(
+            queries,
+            (
+                "match (n:`Human Being`) return n".to_owned(),
+                "match (n:`Human Being` {`lastName`: $last_name}) return n".to_owned(),
+            )
+        );
+    }
+
+    #[test]
+    fn should_leave_out_soft_deleted_nodes() {
+        let queries = with_container(
+            parse_quote! {
+                #[neo4j(soft_delete)]
+                struct Tag {
+                    id: String,
+                }
+            },
+            |cont| {
+                (
+                    get_all_query(cont),
+                    find_by_query(cont, field(cont, "id").unwrap()),
+                )
+            },
+        );
+
+        assert_eq!(
+            queries,
+            (
+                "match (n:`Tag`) where n.`deleted_at` is null return n".to_owned(),
+                "match (n:`Tag` {`id`: $id}) where n.`deleted_at` is null return n".to_owned(),
+            )
+        );
+    }
+}
@@ -1,27 +1,32 @@
 //! Inject function to insert  struct to database.
-use crate::ast::{iter_fields, Container, Ctx};
+use super::{escape, flattened, flattened_fields, labels, param, value};
+use crate::ast::{iter_fields, Container, Ctx, Managed};
 use proc_macro2::TokenStream;
 use quote::quote;
 
-pub(crate) fn gen(cx: &Ctx, cont: &Container) -> TokenStream {
+pub(crate) fn gen(_cx: &Ctx, cont: &Container) -> TokenStream {
     let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let (create_fields, injections): (Vec<String>, Vec<TokenStream>) =
-        iter_fields(cx, cont, |field| {
-            let name = field.attrs.name();
-            let ident = field.original.ident.as_ref()?;
-            let injection = quote!(p.put(#name.into(), self.#ident.clone().into()));
-            let kv = format!("{}: ${}", name, name);
-
-            Some((kv, injection))
-        })
-        .into_iter()
-        .unzip();
-
-    let query = format!("create (_:{} {{{}}})", name, create_fields.join(", "));
+    let injections = iter_fields(cont, |field| {
+        if let Some(Managed::CreatedAt) | Some(Managed::UpdatedAt) = field.attrs.managed() {
+            return None;
+        }
+        let param = param(field);
+        let value = value(field);
+        Some(quote!(p.put(#param.into(), #value)))
+    });
+    let flattened = flattened_fields(cont).into_iter().map(|field| {
+        let param = param(field);
+        let flattened = flattened(field);
+        quote!(p.put(#param.into(), #flattened.into()))
+    });
+    let query = insert_query(cont);
+    let injections = injections.into_iter().chain(flattened);
 
     quote! {
-        impl #name {
+        impl #impl_generics #name #ty_generics #where_clause {
             pub async fn insert(&self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
                 use neo4jrs::Query;
                 let mut p = neo4jrs::types::BoltMap::default() #(; #injections)*;
@@ -31,3 +36,108 @@ pub(crate) fn gen(cx: &Ctx, cont: &Container) -> TokenStream {
         }
     }
 }
+
+/// The query creating the node, e.g. `create (_:Person {id: $id, name: $name})`
+fn insert_query(cont: &Container) -> String {
+    let create_fields = iter_fields(cont, |field| {
+        let property = escape(field.attrs.name());
+        match field.attrs.managed() {
+            Some(Managed::CreatedAt) | Some(Managed::UpdatedAt) => {
+                Some(format!("{}: datetime()", property))
+            }
+            _ => Some(format!("{}: ${}", property, param(field))),
+        }
+    });
+    let sets = flattened_fields(cont)
+        .into_iter()
+        .map(|field| format!(" set n += ${}", param(field)))
+        .collect::<Vec<_>>();
+
+    if sets.is_empty() {
+        format!(
+            "create (_{} {{{}}})",
+            labels(cont),
+            create_fields.join(", ")
+        )
+    } else {
+        format!(
+            "create (n{} {{{}}}){}",
+            labels(cont),
+            create_fields.join(", "),
+            sets.concat()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label::with_container;
+    use syn::parse_quote;
+
+    #[test]
+    fn should_create_the_node_with_its_properties() {
+        let query = with_container(
+            parse_quote! {
+                #[neo4j(rename_all = "camelCase")]
+                struct Person {
+                    user_id: String,
+                    #[neo4j(rename = "first-name")]
+                    first_name: String,
+                    #[neo4j(skip)]
+                    cache: Vec<String>,
+                    #[neo4j(relation = "WORKS_AT")]
+                    employer: Option<Company>,
+                }
+            },
+            insert_query,
+        );
+
+        assert_eq!(
+            query,
+            "create (_:`Person` {`userId`: $user_id, `first-name`: $first_name})"
+        );
+    }
+
+    #[test]
+    fn should_stamp_timestamps_and_set_flattened_properties() {
+        let query = with_container(
+            parse_quote! {
+                #[neo4j(rename_all = "camelCase")]
+                struct Document {
+                    id: i64,
+                    #[neo4j(version)]
+                    version: i64,
+                    #[neo4j(created_at)]
+                    created_at: Option<String>,
+                    #[neo4j(updated_at)]
+                    updated_at: Option<String>,
+                    #[neo4j(flatten)]
+                    source: Source,
+                }
+            },
+            insert_query,
+        );
+
+        assert_eq!(
+            query,
+            "create (n:`Document` {`id`: $id, `version`: $version, `createdAt`: datetime(), `updatedAt`: datetime()}) set n += $source"
+        );
+    }
+
+    #[test]
+    fn should_quote_names_and_map_tuple_structs_by_index() {
+        let query = with_container(
+            parse_quote! {
+                #[neo4j(label = "Order", identifier = "0")]
+                struct Order(String, #[neo4j(rename = "return")] bool, #[neo4j(rename = "café")] String);
+            },
+            insert_query,
+        );
+
+        assert_eq!(
+            query,
+            "create (_:`Order` {`0`: $0, `return`: $1, `café`: $2})"
+        );
+    }
+}
@@ -1,31 +1,215 @@
-//! Inject function to update or insert struct to database.
-use crate::ast::{Container, Ctx};
+//! Inject functions to update or insert struct to database in a single `MERGE`.
+use super::{escape, flattened, flattened_fields, is_identifier, key, labels, param, value};
+use crate::ast::{iter_fields, Container, Ctx, Data, Managed, Policy};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::quote;
 
 pub(crate) fn gen(_cx: &Ctx, cont: &Container) -> TokenStream {
-    let identifier = cont.attrs.identifier();
-    let identifier_ident = format_ident!("{identifier}");
+    let Upsert {
+        query,
+        params,
+        props,
+    } = match upsert(cont) {
+        Some(upsert) => upsert,
+        None => return TokenStream::new(),
+    };
     let name = cont.ident();
-    let find_query = format!(
-        "match (n:{name} {{{}: ${}}}) return n.id",
-        identifier, identifier
-    );
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let sync = if has_relations(cont) {
+        quote!(self.sync_relations(graph).await?;)
+    } else {
+        TokenStream::new()
+    };
 
     quote! {
-        impl #name {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Creates the node or updates the existing one, matched by its identifiers, then
+            /// syncs the relationships of the relation fields. The node and the relationships are
+            /// written by separate queries, pass a `Txn` to write them atomically
             pub async fn persist(&self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
-                let find_query = neo4jrs::Query::new(#find_query).param(#identifier, self.#identifier_ident.clone());
-
-                if let Ok(mut result) = graph.execute(find_query).await {
-                    if let Ok(Some(row)) = result.next().await {
-                        if row.get::<neo4jrs::types::BoltString>("n.id").is_some() {
-                            return self.update(graph).await
-                        }
-                    }
+                graph.run(self.upsert_query()).await?;
+                #sync
+                Ok(())
+            }
+
+            /// Same as `persist`, returning the node as stored in the database, without its
+            /// relation fields
+            pub async fn upsert(&self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Self> {
+                let mut result = graph.execute(self.upsert_query()).await?;
+                let node = match result.next().await?.and_then(|row| row.get::<neo4jrs::Node>("n")) {
+                    Some(n) => <Self as std::convert::TryFrom<neo4jrs::Node>>::try_from(n)?,
+                    None => return Err(neo4jrs::Error::NoMatch),
+                };
+                #sync
+                Ok(node)
+            }
+
+            fn upsert_query(&self) -> neo4jrs::Query {
+                let mut props = neo4jrs::types::BoltMap::default() #(; #props)*;
+                neo4jrs::Query::new(#query) #(#params)*
+            }
+        }
+    }
+}
+
+/// The `merge` query of `persist` and `upsert`, along with the calls adding its parameters and
+/// the properties always written, sent as `$props`
+struct Upsert {
+    query: String,
+    params: Vec<TokenStream>,
+    props: Vec<TokenStream>,
+}
+
+fn upsert(cont: &Container) -> Option<Upsert> {
+    let (key, mut params) = key(cont)?;
+    let mut on_create = vec![];
+    let mut on_match = vec![];
+    let mut props = vec![];
+    let mut stamps = vec![];
+    iter_fields(cont, |field| {
+        if is_identifier(cont, field) {
+            return None;
+        }
+        let value = value(field);
+        let property = field.attrs.name();
+        match field.attrs.managed() {
+            Some(Managed::Version) => {
+                let param = param(field);
+                let escaped = escape(property);
+                on_create.push(format!("n.{} = ${}", escaped, param));
+                on_match.push(format!("n.{} = n.{} + 1", escaped, escaped));
+                params.push(quote!(.param(#param, #value)));
+                return Some(());
+            }
+            Some(Managed::CreatedAt) => {
+                on_create.push(format!("n.{} = datetime()", escape(property)));
+                return Some(());
+            }
+            Some(Managed::UpdatedAt) => {
+                stamps.push(format!("n.{} = datetime()", escape(property)));
+                return Some(());
+            }
+            None => {}
+        }
+        match field.attrs.policy() {
+            Policy::Always => props.push(quote!(props.put(#property.into(), #value))),
+            policy => {
+                let param = param(field);
+                let set = format!("n.{} = ${}", escape(property), param);
+                if policy == Policy::OnCreate {
+                    on_create.push(set);
+                } else {
+                    on_match.push(set);
                 }
-                self.insert(graph).await
+                params.push(quote!(.param(#param, #value)));
+            }
+        }
+        Some(())
+    });
+    props.extend(flattened_fields(cont).into_iter().map(|field| {
+        let flattened = flattened(field);
+        quote! {
+            for (key, value) in #flattened {
+                props.put(key, value)
             }
         }
+    }));
+
+    let mut query = format!("merge (n{} {})", labels(cont), key);
+    if !on_create.is_empty() {
+        query.push_str(&format!(" on create set {}", on_create.join(", ")));
+    }
+    if !on_match.is_empty() {
+        query.push_str(&format!(" on match set {}", on_match.join(", ")));
+    }
+    if !props.is_empty() {
+        query.push_str(" set n += $props");
+        params.push(quote!(.param("props", props)));
+    }
+    if !stamps.is_empty() {
+        query.push_str(&format!(" set {}", stamps.join(", ")));
+    }
+    query.push_str(" return n");
+
+    Some(Upsert {
+        query,
+        params,
+        props,
+    })
+}
+
+fn has_relations(cont: &Container) -> bool {
+    let Data::Struct(_, fields) = &cont.data;
+    fields
+        .iter()
+        .any(|field| !field.attrs.skip() && field.attrs.relation().is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label::with_container;
+    use syn::parse_quote;
+
+    fn upsert_query(input: syn::DeriveInput) -> String {
+        with_container(input, |cont| upsert(cont).unwrap().query)
+    }
+
+    #[test]
+    fn should_write_on_create_and_on_match_properties_in_a_single_merge() {
+        let query = upsert_query(parse_quote! {
+            #[neo4j(identifier = ["org", "email"])]
+            struct User {
+                org: String,
+                email: String,
+                name: String,
+                #[neo4j(on_create, rename = "createdAt")]
+                created_at: i64,
+                #[neo4j(on_match)]
+                seen: i64,
+            }
+        });
+
+        assert_eq!(
+            query,
+            "merge (n:`User` {`org`: $org, `email`: $email}) on create set n.`createdAt` = $created_at on match set n.`seen` = $seen set n += $props return n"
+        );
+    }
+
+    #[test]
+    fn should_increment_the_version_and_stamp_timestamps() {
+        let query = upsert_query(parse_quote! {
+            #[neo4j(rename_all = "camelCase")]
+            struct Document {
+                id: i64,
+                body: String,
+                #[neo4j(version)]
+                version: i64,
+                #[neo4j(created_at)]
+                created_at: Option<String>,
+                #[neo4j(updated_at)]
+                updated_at: Option<String>,
+            }
+        });
+
+        assert_eq!(
+            query,
+            "merge (n:`Document` {`id`: $id}) on create set n.`version` = $version, n.`createdAt` = datetime() on match set n.`version` = n.`version` + 1 set n += $props set n.`updatedAt` = datetime() return n"
+        );
+    }
+
+    #[test]
+    fn should_merge_only_identifiers() {
+        let query = upsert_query(parse_quote! {
+            struct Tag {
+                id: String,
+                #[neo4j(relation = "TAGS")]
+                tagged: Vec<Post>,
+            }
+        });
+
+        assert_eq!(query, "merge (n:`Tag` {`id`: $id}) return n");
     }
 }
@@ -0,0 +1,184 @@
+//! Inject functions loading and syncing the fields holding related nodes,
+//! `#[neo4j(relation = "...")]`.
+use super::key;
+use crate::ast::{Container, Ctx, Data, Direction, Field};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+pub(crate) fn gen(cx: &Ctx, cont: &Container) -> TokenStream {
+    let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let relations = relations(Some(cx), cont);
+    if relations.is_empty() || key(cont).is_none() {
+        return TokenStream::new();
+    }
+
+    let loads = relations.iter().map(|relation| {
+        let load = format_ident!("load_{}", relation.member_name);
+        let query = relation.query();
+        let assign = relation.assign(quote!(self));
+        let doc = format!(
+            "Loads `{}` with the nodes related through `{}`",
+            relation.member_name, relation.name
+        );
+        quote! {
+            #[doc = #doc]
+            pub async fn #load(&mut self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
+                let mut nodes = vec![];
+                let mut result = graph.execute(#query).await?;
+                while let Some(row) = result.next().await? {
+                    if let Some(n) = row.get::<neo4jrs::Node>("n") {
+                        nodes.push(n);
+                    }
+                }
+                #assign
+                Ok(())
+            }
+        }
+    });
+
+    let syncs = relations.iter().map(|relation| {
+        let member = relation.member;
+        let ty = relation.ty;
+        let relation_name = &relation.name;
+        let direction = &relation.direction;
+        quote! {
+            let queries = <Self as neo4jrs::NodeEntity>::sync_related_queries::<#ty, _>(
+                self,
+                #relation_name,
+                #direction,
+                self.#member.iter(),
+            );
+            for query in queries {
+                graph.run(query).await?;
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#loads)*
+
+            /// Makes the related nodes of the relation fields the only ones in the database
+            async fn sync_relations(&self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
+                #(#syncs)*
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The `include_<field>` methods of the typed finder, returning the related nodes along with
+/// each node
+pub(crate) fn includes(cont: &Container) -> Vec<TokenStream> {
+    let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    relations(None, cont)
+        .iter()
+        .map(|relation| {
+            let column = relation.member_name.clone();
+            let include = format_ident!("include_{}", column);
+            let ty = relation.ty;
+            let relation_name = &relation.name;
+            let direction = &relation.direction;
+            let assign = relation.assign(quote!(node));
+            quote! {
+                pub fn #include(self) -> Self {
+                    fn fill #impl_generics (node: &mut #name #ty_generics, nodes: Vec<neo4jrs::Node>) -> neo4jrs::Result<()> #where_clause {
+                        #assign
+                        Ok(())
+                    }
+
+                    Self {
+                        inner: self.inner.include(
+                            #column,
+                            #relation_name,
+                            #direction,
+                            <#ty as neo4jrs::NodeEntity>::LABELS,
+                            <#ty as neo4jrs::NodeEntity>::SOFT_DELETE,
+                            fill,
+                        ),
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+struct RelationField<'a> {
+    member: &'a syn::Member,
+    /// Name of the field in the generated methods
+    member_name: String,
+    /// Type of the related nodes
+    ty: &'a syn::Type,
+    /// Whether the field is a `Vec`, or an `Option`
+    many: bool,
+    name: String,
+    direction: TokenStream,
+}
+
+impl<'a> RelationField<'a> {
+    fn query(&self) -> TokenStream {
+        let ty = self.ty;
+        let name = &self.name;
+        let direction = &self.direction;
+        quote! {
+            <Self as neo4jrs::NodeEntity>::related_query::<#ty>(self, #name, #direction)
+        }
+    }
+
+    /// Converts `nodes` and stores them in the field of `target`
+    fn assign(&self, target: TokenStream) -> TokenStream {
+        let member = self.member;
+        let ty = self.ty;
+        let related = quote! {
+            nodes
+                .into_iter()
+                .map(<#ty as std::convert::TryFrom<neo4jrs::Node>>::try_from)
+                .collect::<neo4jrs::Result<Vec<#ty>>>()?
+        };
+        if self.many {
+            quote!(#target.#member = #related;)
+        } else {
+            quote!(#target.#member = #related.into_iter().next();)
+        }
+    }
+}
+
+/// The relation fields, reporting those of the wrong type when given a context
+fn relations<'a>(cx: Option<&Ctx>, cont: &'a Container) -> Vec<RelationField<'a>> {
+    let Data::Struct(_, fields) = &cont.data;
+    fields
+        .iter()
+        .filter(|field| !field.attrs.skip())
+        .filter_map(|field| relation(cx, field))
+        .collect()
+}
+
+fn relation<'a>(cx: Option<&Ctx>, field: &'a Field) -> Option<RelationField<'a>> {
+    let relation = field.attrs.relation()?;
+    let (ty, many) = match field.related_ty() {
+        Some(related) => related,
+        None => {
+            if let Some(cx) = cx {
+                let msg = "relation fields hold the related nodes in an Option or a Vec";
+                cx.error_spanned_by(field.ty, msg);
+            }
+            return None;
+        }
+    };
+    let direction = match relation.direction {
+        Direction::Outgoing => quote!(neo4jrs::Direction::Outgoing),
+        Direction::Incoming => quote!(neo4jrs::Direction::Incoming),
+    };
+    Some(RelationField {
+        member: &field.member,
+        member_name: field.member_name(),
+        ty,
+        many,
+        name: relation.name.clone(),
+        direction,
+    })
+}
@@ -0,0 +1,186 @@
+//! Inject functions creating the constraints and indexes of the nodes.
+use super::{escape, identifiers, key};
+use crate::ast::{Container, Ctx, Data};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn gen(cx: &Ctx, cont: &Container) -> TokenStream {
+    let Data::Struct(_, fields) = &cont.data;
+    for field in fields {
+        if !field.attrs.is_property() && !field.attrs.schema().is_empty() {
+            let msg = "constraints and indexes are only supported on properties";
+            cx.error_spanned_by(field.original, msg);
+        }
+    }
+    if key(cont).is_none() {
+        return TokenStream::new();
+    }
+
+    let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let statements = statements(cont);
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The statements creating the constraints and indexes of the nodes, all idempotent
+            pub fn schema_statements() -> &'static [&'static str] {
+                &[#(#statements),*]
+            }
+
+            /// Creates the constraints and indexes of the nodes which don't exist yet
+            pub async fn ensure_schema(graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
+                for statement in Self::schema_statements() {
+                    graph.run(neo4jrs::Query::new(statement)).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The statements creating the constraints and indexes: the uniqueness of the identifiers, then
+/// those of each property, then the fulltext index of all the fulltext properties
+fn statements(cont: &Container) -> Vec<String> {
+    let Data::Struct(_, fields) = &cont.data;
+    let label = &cont.attrs.labels()[0];
+    let identifiers = identifiers(cont)
+        .into_iter()
+        .map(|field| field.attrs.name())
+        .collect::<Vec<_>>();
+
+    let mut statements = vec![unique(label, &identifiers)];
+    let mut fulltext = vec![];
+    for field in fields.iter().filter(|field| field.attrs.is_property()) {
+        let schema = field.attrs.schema();
+        let property = field.attrs.name();
+        let is_identifier = identifiers == [property];
+        if schema.unique && !is_identifier {
+            statements.push(unique(label, &[property]));
+        }
+        if schema.required {
+            statements.push(format!(
+                "create constraint {} if not exists for (n:{}) require n.{} is not null",
+                schema_name(label, &[property], "required"),
+                escape(label),
+                escape(property)
+            ));
+        }
+        if schema.index && !schema.unique && !is_identifier {
+            statements.push(format!(
+                "create index {} if not exists for (n:{}) on (n.{})",
+                schema_name(label, &[property], "index"),
+                escape(label),
+                escape(property)
+            ));
+        }
+        if schema.fulltext {
+            fulltext.push(format!("n.{}", escape(property)));
+        }
+    }
+    if !fulltext.is_empty() {
+        statements.push(format!(
+            "create fulltext index {} if not exists for (n:{}) on each [{}]",
+            schema_name(label, &[], "fulltext"),
+            escape(label),
+            fulltext.join(", ")
+        ));
+    }
+    statements
+}
+
+/// The uniqueness constraint of the properties, e.g. `require (n.org, n.email) is unique`
+fn unique(label: &str, properties: &[&str]) -> String {
+    let escaped = properties
+        .iter()
+        .map(|property| format!("n.{}", escape(property)))
+        .collect::<Vec<_>>();
+    let required = match escaped.as_slice() {
+        [property] => property.clone(),
+        _ => format!("({})", escaped.join(", ")),
+    };
+    format!(
+        "create constraint {} if not exists for (n:{}) require {} is unique",
+        schema_name(label, properties, "unique"),
+        escape(label),
+        required
+    )
+}
+
+/// Name of a constraint or index, e.g. `person_email_unique`
+fn schema_name(label: &str, properties: &[&str], kind: &str) -> String {
+    let mut parts = vec![label];
+    parts.extend(properties);
+    parts.push(kind);
+    parts
+        .join("_")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label::with_container;
+    use syn::parse_quote;
+
+    #[test]
+    fn should_generate_schema_statements() {
+        let statements = with_container(
+            parse_quote! {
+                #[neo4j(labels = ["User", "Account"], identifier = ["org", "email"])]
+                struct User {
+                    org: String,
+                    email: String,
+                    #[neo4j(unique, index)]
+                    handle: String,
+                    #[neo4j(required, index, rename = "full name")]
+                    name: String,
+                    #[neo4j(fulltext)]
+                    bio: String,
+                    #[neo4j(fulltext)]
+                    name_notes: String,
+                }
+            },
+            statements,
+        );
+
+        assert_eq!(
+            statements,
+            vec![
+                "create constraint user_org_email_unique if not exists for (n:`User`) require (n.`org`, n.`email`) is unique",
+                "create constraint user_handle_unique if not exists for (n:`User`) require n.`handle` is unique",
+                "create constraint user_full_name_required if not exists for (n:`User`) require n.`full name` is not null",
+                "create index user_full_name_index if not exists for (n:`User`) on (n.`full name`)",
+                "create fulltext index user_fulltext if not exists for (n:`User`) on each [n.`bio`, n.`name_notes`]",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_index_the_identifier_twice() {
+        let statements = with_container(
+            parse_quote! {
+                struct Tag {
+                    #[neo4j(index)]
+                    id: String,
+                }
+            },
+            statements,
+        );
+
+        assert_eq!(
+            statements,
+            vec![
+                "create constraint tag_id_unique if not exists for (n:`Tag`) require n.`id` is unique"
+            ]
+        );
+    }
+}
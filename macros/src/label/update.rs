@@ -1,39 +1,182 @@
-use crate::ast::{iter_fields, Container, Ctx};
+use super::{escape, flattened, flattened_fields, key, labels, managed, param, value};
+use crate::ast::{iter_fields, Container, Ctx, Managed};
 use proc_macro2::TokenStream;
 use quote::quote;
 
 /// Inject pub update function to insert struct to graph db
-pub fn gen(cx: &Ctx, cont: &Container) -> TokenStream {
-    let identifier = cont.attrs.identifier();
+pub fn gen(_cx: &Ctx, cont: &Container) -> TokenStream {
+    let update_query = match update_query(cont) {
+        Some(query) => query,
+        None => return TokenStream::new(),
+    };
     let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let injections = iter_fields(cont, |field| {
+        if field.attrs.managed().is_some() {
+            return None;
+        }
+        let param = param(field);
+        let value = value(field);
+        Some(quote!(p.put(#param.into(), #value)))
+    });
+    let flattened = flattened_fields(cont).into_iter().map(|field| {
+        let param = param(field);
+        let flattened = flattened(field);
+        quote!(p.put(#param.into(), #flattened.into()))
+    });
+    let version = managed(cont, Managed::Version).map(|field| {
+        let param = param(field);
+        let value = value(field);
+        (&field.member, quote!(p.put(#param.into(), #value)))
+    });
+    let version_param = version.as_ref().map(|(_, param)| param.clone());
+    let injections = injections.into_iter().chain(flattened).chain(version_param);
 
-    let (fields_kv, injections): (Vec<String>, Vec<TokenStream>) = iter_fields(cx, cont, |field| {
-        let field_name = field.attrs.name();
-        let fident = field.original.ident.as_ref()?;
-        let injection = quote!(p.put(#field_name.into(), self.#fident.clone().into()));
-        let kv = format!("set n.{} = ${}", field_name, field_name);
-
-        Some((kv, injection))
-    })
-    .into_iter()
-    .unzip();
-
-    let update_query = format!(
-        "match (n:{name} {{{}: ${}}}) {} return n",
-        identifier,
-        identifier,
-        fields_kv.join("  ")
-    );
-
-    let expanded = quote! {
-        impl #name {
-            pub async fn update(&self, graph: &impl neo4jrs::Execute) -> Result<(), neo4jrs::Error> {
-                let mut p = neo4jrs::types::BoltMap::default() #(; #injections)*;
-                let query = neo4jrs::Query::new_with_params(#update_query, p);
-                graph.run(query).await
+    match version {
+        Some((member, _)) => {
+            let target = name.to_string();
+            quote! {
+                impl #impl_generics #name #ty_generics #where_clause {
+                    /// Writes the struct to its node, if the node still has the version of the
+                    /// struct, then increments the version of both, failing with
+                    /// `Error::VersionConflict` otherwise, or `Error::NoMatch` when the node
+                    /// doesn't exist
+                    pub async fn update(&mut self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
+                        let mut p = neo4jrs::types::BoltMap::default() #(; #injections)*;
+                        let query = neo4jrs::Query::new_with_params(#update_query, p);
+                        match graph.execute(query).await?.next().await? {
+                            Some(row) if row.get::<bool>("current") == Some(true) => {
+                                self.#member += 1;
+                                Ok(())
+                            }
+                            Some(_) => Err(neo4jrs::Error::VersionConflict {
+                                target: #target.to_owned(),
+                            }),
+                            None => Err(neo4jrs::Error::NoMatch),
+                        }
+                    }
+                }
             }
         }
+        None => quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Writes the struct to its node, failing with `Error::NoMatch` when it doesn't
+                /// exist
+                pub async fn update(&self, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
+                    let mut p = neo4jrs::types::BoltMap::default() #(; #injections)*;
+                    let query = neo4jrs::Query::new_with_params(#update_query, p);
+                    match graph.execute(query).await?.next().await? {
+                        Some(_) => Ok(()),
+                        None => Err(neo4jrs::Error::NoMatch),
+                    }
+                }
+            }
+        },
+    }
+}
+
+/// The query writing the struct to its node. With a version, the node is matched on its key alone
+/// and only written when it has the version of the struct, the query returns whether it had it as
+/// `current`, to tell a missing node from a conflicting version.
+fn update_query(cont: &Container) -> Option<String> {
+    let (key, _) = key(cont)?;
+    let fields_kv = iter_fields(cont, |field| {
+        if field.attrs.managed().is_some() {
+            return None;
+        }
+        Some(format!(
+            "set n.{} = ${}",
+            escape(field.attrs.name()),
+            param(field)
+        ))
+    });
+    let sets = flattened_fields(cont)
+        .into_iter()
+        .map(|field| format!(" set n += ${}", param(field)))
+        .collect::<String>();
+
+    let version = managed(cont, Managed::Version);
+    let mut stamps = String::new();
+    if let Some(field) = version {
+        let property = escape(field.attrs.name());
+        stamps.push_str(&format!(" set n.{} = n.{} + 1", property, property));
+    }
+    if let Some(field) = managed(cont, Managed::UpdatedAt) {
+        stamps.push_str(&format!(
+            " set n.{} = datetime()",
+            escape(field.attrs.name())
+        ));
+    }
+
+    let updates = format!("{}{}{}", fields_kv.join("  "), sets, stamps);
+    let query = match version {
+        Some(field) => format!(
+            "match (n{} {}) with n, n.{} = ${} as current foreach (x in case when current then [1] else [] end | {}) return current",
+            labels(cont),
+            key,
+            escape(field.attrs.name()),
+            param(field),
+            updates.trim_start()
+        ),
+        None => format!("match (n{} {}) {} return n", labels(cont), key, updates),
     };
+    Some(query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::label::with_container;
+    use syn::parse_quote;
+
+    #[test]
+    fn should_set_the_properties_of_the_node() {
+        let query = with_container(
+            parse_quote! {
+                #[neo4j(rename_all = "camelCase", identifier = "user_id")]
+                struct Person {
+                    user_id: String,
+                    #[neo4j(rename = "first-name")]
+                    first_name: String,
+                    #[neo4j(skip)]
+                    cache: Vec<String>,
+                    #[neo4j(flatten)]
+                    address: Address,
+                }
+            },
+            update_query,
+        );
+
+        assert_eq!(
+            query.unwrap(),
+            "match (n:`Person` {`userId`: $user_id}) set n.`userId` = $user_id  set n.`first-name` = $first_name set n += $address return n"
+        );
+    }
+
+    #[test]
+    fn should_only_write_nodes_with_the_version_of_the_struct() {
+        let query = with_container(
+            parse_quote! {
+                #[neo4j(rename_all = "camelCase")]
+                struct Document {
+                    id: i64,
+                    body: String,
+                    #[neo4j(version)]
+                    version: i64,
+                    #[neo4j(created_at)]
+                    created_at: Option<String>,
+                    #[neo4j(updated_at)]
+                    updated_at: Option<String>,
+                }
+            },
+            update_query,
+        );
 
-    expanded.into()
+        assert_eq!(
+            query.unwrap(),
+            "match (n:`Document` {`id`: $id}) with n, n.`version` = $version as current foreach (x in case when current then [1] else [] end | set n.`id` = $id  set n.`body` = $body set n.`version` = n.`version` + 1 set n.`updatedAt` = datetime()) return current"
+        );
+    }
 }
@@ -1,6 +1,10 @@
 mod ast;
 mod bolt_struct;
+mod cypher;
 mod label;
+mod properties;
+mod relationship;
+mod value;
 
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
@@ -15,6 +19,140 @@ pub fn derive_boltstruct(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Maps a struct to nodes, generating `insert`, `update`, `persist`, `upsert`, `delete` and
+/// finders, along with `insert_many`, `upsert_many` and `delete_where_<field>(value, graph)`.
+/// `delete` and `delete_where_<field>` return the number of nodes deleted, there is no
+/// `delete_where_<field>` for versions, timestamps and the soft delete property.
+///
+/// `persist` and `upsert` run a single `MERGE` on the identifier fields, creating the node or
+/// setting the other fields on the existing one. `insert_many` and `upsert_many` do the same for
+/// a slice of structs, sending them in chunks with one `UNWIND` query per chunk, and return the
+/// number of nodes written.
+///
+/// `find()` returns a typed finder, named after the struct, e.g. `PersonFinder`, with
+/// `where_<field>`, `where_<field>_gt`, `where_<field>_starts_with`, ..., `order_by_<field>`,
+/// `skip` and `limit`, run with `all`, `first`, `count` or `exists`. Values are sent as query
+/// parameters.
+///
+/// ```ignore
+/// let adults = Person::find()
+///     .where_age_gte(18)
+///     .where_name_starts_with("A")
+///     .order_by_name()
+///     .skip(20)
+///     .limit(10)
+///     .all(&graph)
+///     .await?;
+/// ```
+///
+/// The struct is read with the generated `TryFrom<Node>`, or `TryFrom<Row>` from the columns of
+/// a row, e.g. `return p.name as name`. A missing property fails with `Error::MissingProperty`,
+/// unless the field is an `Option` or has a default, and a property of the wrong type fails with
+/// `Error::InvalidProperty`.
+///
+/// Fields holding related nodes, `Option<T>` or `Vec<T>` of another `Label` struct, are loaded
+/// with `load_<field>(&mut self, graph)`, or along with the nodes of a finder with
+/// `include_<field>()`. `persist` and `upsert` then sync the relationships: those to other nodes
+/// are deleted, and those to the nodes of the field are merged. The related nodes must exist.
+/// The sync runs its own queries after the `MERGE` of the node, so it isn't atomic when given a
+/// `Graph`: if it fails the node is written but its relationships may not be. Run them in a
+/// transaction to write both or neither:
+///
+/// ```ignore
+/// let txn = graph.start_txn().await?;
+/// person.persist(&txn).await?;
+/// txn.commit().await?;
+/// ```
+///
+/// ```ignore
+/// #[derive(Label)]
+/// struct Person {
+///     id: i64,
+///     #[neo4j(relation = "WORKS_AT")]
+///     employer: Option<Company>,
+///     #[neo4j(relation = "MANAGES", direction = "in")]
+///     managers: Vec<Person>,
+/// }
+///
+/// let people = Person::find().include_employer().all(&graph).await?;
+/// ```
+///
+/// With a `#[neo4j(version)]` field, `update` takes `&mut self` and only writes the node when it
+/// still has the version of the struct, incrementing both, and fails with
+/// `Error::VersionConflict` otherwise. `persist`, `upsert` and `upsert_many` increment the version
+/// of existing nodes without checking it. With or without one, `update` fails with
+/// `Error::NoMatch` when the node doesn't exist.
+///
+/// ```ignore
+/// #[derive(Label)]
+/// struct Document {
+///     id: i64,
+///     body: String,
+///     #[neo4j(version)]
+///     version: i64,
+///     #[neo4j(created_at)]
+///     created_at: Option<DateTime<FixedOffset>>,
+///     #[neo4j(updated_at)]
+///     updated_at: Option<DateTime<FixedOffset>>,
+/// }
+///
+/// match document.update(&graph).await {
+///     Err(Error::VersionConflict { .. }) => { /* read it again and retry */ }
+///     result => result?,
+/// }
+/// ```
+///
+/// Generic structs get the bounds their fields need, e.g. `T: Clone + Into<BoltType>` and
+/// `T: TryFrom<BoltType>` for a `T` property. Structs with lifetimes, views borrowing their
+/// fields such as `name: &'a str`, only get the methods writing nodes: `insert`, `update`,
+/// `delete`, `insert_many`, `upsert_many` and `ensure_schema`. The fields of tuple structs are
+/// named by their index, e.g. `#[neo4j(identifier = "0")]`, unless renamed.
+///
+/// `ensure_schema(graph)` creates the constraints and indexes which don't exist yet: a uniqueness
+/// constraint on the identifier fields, and those declared on the fields. They are created for the
+/// first label, `schema_statements()` returns the statements for review.
+///
+/// Container attributes:
+/// - `#[neo4j(label = "Person")]` or `#[neo4j(labels = ["Person", "Employee"])]` set the node
+///   labels, the struct name by default.
+/// - `#[neo4j(rename_all = "camelCase")]` renames the properties of all fields, one of
+///   `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`,
+///   `kebab-case` and `SCREAMING-KEBAB-CASE`.
+/// - `#[neo4j(identifier = "id")]` or `#[neo4j(identifier = ["org", "email"])]` the fields
+///   identifying nodes, `id` by default.
+/// - `#[neo4j(default)]` or `#[neo4j(default = "path")]` the default of missing fields.
+/// - `#[neo4j(chunk_size = 1000)]` the number of structs sent per query by `insert_many` and
+///   `upsert_many`, 1000 by default.
+/// - `#[neo4j(detach_delete)]` also deletes the relationships of the deleted nodes, without it
+///   deleting a node which still has relationships fails.
+/// - `#[neo4j(soft_delete)]` or `#[neo4j(soft_delete = "deletedAt")]` sets the property,
+///   `deleted_at` by default, to `datetime()` instead of deleting nodes, and hides the nodes where
+///   it is set from the generated finders, the relation fields loaded with `load_<field>` and
+///   `include_<field>`, and the nodes returned by `outgoing` and `incoming` of `Relationship`
+///   structs. Syncing the relation fields in `persist` and `upsert` keeps the relationships to
+///   these nodes.
+///
+/// Field attributes:
+/// - `#[neo4j(rename = "first_name")]` the name of the property.
+/// - `#[neo4j(skip)]` never writes the field, which is read as its default.
+/// - `#[neo4j(on_create)]` or `#[neo4j(on_match)]` only writes the field in `persist` and
+///   `upsert` when the node is created, respectively when it already exists.
+/// - `#[neo4j(default)]` or `#[neo4j(default = "path")]` the default when the property is missing.
+/// - `#[neo4j(with = "module")]` converts the value with `module::serialize(&T) -> BoltType` and
+///   `module::deserialize(BoltType) -> Result<T, E>`, or only one way with
+///   `#[neo4j(serialize_with = "path")]` and `#[neo4j(deserialize_with = "path")]`. `T` is the
+///   type of the field, or `T` for `Option<T>`.
+/// - `#[neo4j(flatten)]` embeds the properties of a struct deriving `Properties`.
+/// - `#[neo4j(relation = "WORKS_AT")]` holds the nodes related through relationships of this type
+///   instead of a property, with `#[neo4j(direction = "in")]` for incoming relationships, `"out"`
+///   by default.
+/// - `#[neo4j(unique)]` a uniqueness constraint on the property, which is also indexed.
+/// - `#[neo4j(required)]` an existence constraint on the property, which requires Neo4j Enterprise.
+/// - `#[neo4j(index)]` a range index on the property.
+/// - `#[neo4j(fulltext)]` adds the property to the fulltext index of the nodes.
+/// - `#[neo4j(version)]` the version of the node, an integer, see above.
+/// - `#[neo4j(created_at)]` and `#[neo4j(updated_at)]` set to `datetime()` by the database when
+///   the node is created, respectively written, the value of the field is never sent.
 #[proc_macro_derive(Label, attributes(neo4j))]
 #[proc_macro_error]
 pub fn derive_label(input: TokenStream) -> TokenStream {
@@ -23,6 +161,121 @@ pub fn derive_label(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Maps a struct to relationships between two kinds of nodes, both deriving `Label`, generating
+/// `connect`, `disconnect`, `update`, `outgoing` and `incoming`, along with `TryFrom<Relation>` and
+/// `TryFrom<UnboundedRelation>`.
+///
+/// The nodes are matched on their identifier fields. `outgoing` returns the relationships leaving
+/// a node along with their end nodes, `incoming` those reaching a node along with their start
+/// nodes.
+///
+/// ```ignore
+/// #[derive(Relationship)]
+/// #[neo4j(type = "FOLLOWS", from = Person, to = Person)]
+/// struct Follows {
+///     since: i64,
+/// }
+///
+/// Follows { since: 2021 }.connect(&alice, &bob, &graph).await?;
+/// for (follows, person) in Follows::outgoing(&alice, &graph).await? {
+///     println!("{} since {}", person.name, follows.since);
+/// }
+/// ```
+///
+/// Container attributes:
+/// - `#[neo4j(from = Person, to = Company)]` the start and end nodes, required.
+/// - `#[neo4j(type = "WORKS_AT")]` the relationship type, the struct name in
+///   `SCREAMING_SNAKE_CASE` by default.
+/// - `#[neo4j(rename_all = "...")]` and `#[neo4j(default)]` as for `Label`.
+///
+/// Properties take the same field attributes as `Label`, except `on_create`, `on_match`,
+/// `relation`, versions and timestamps.
+#[proc_macro_derive(Relationship, attributes(neo4j))]
+#[proc_macro_error]
+pub fn derive_relationship(input: TokenStream) -> TokenStream {
+    relationship::expand(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(to_stream)
+        .into()
+}
+
+/// Stores the fields of a struct as properties of the nodes or relationships embedding it with
+/// `#[neo4j(flatten)]`, implementing `neo4jrs::Properties`.
+///
+/// ```ignore
+/// #[derive(Properties)]
+/// struct Address {
+///     street: String,
+///     city: String,
+/// }
+///
+/// #[derive(Label)]
+/// struct Person {
+///     id: i64,
+///     #[neo4j(flatten)]
+///     address: Address,
+/// }
+/// ```
+///
+/// Fields take the same attributes as `Label` properties, except `on_create`, `on_match`,
+/// `relation`, constraints, indexes, versions and timestamps, and the container takes `rename_all` and `default`.
+#[proc_macro_derive(Properties, attributes(neo4j))]
+#[proc_macro_error]
+pub fn derive_properties(input: TokenStream) -> TokenStream {
+    properties::expand(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(to_stream)
+        .into()
+}
+
+/// Converts an enum of unit variants, or a newtype struct, to and from a `BoltType`, so that it
+/// can be a field of a `Label` struct.
+///
+/// Variants are stored as strings, their names unless renamed with `#[neo4j(rename = "...")]` or
+/// `#[neo4j(rename_all = "...")]` on the enum, other strings fail with a
+/// `neo4jrs::Error::DeserializationError` naming the enum. Newtype structs are stored as the value
+/// they wrap, whose conversion errors are returned as `neo4jrs::Error::InvalidValue`.
+///
+/// ```ignore
+/// #[derive(Value, Clone)]
+/// #[neo4j(rename_all = "lowercase")]
+/// enum Status {
+///     Active,
+///     Suspended,
+/// }
+///
+/// #[derive(Value, Clone)]
+/// struct Email(String);
+/// ```
+#[proc_macro_derive(Value, attributes(neo4j))]
+#[proc_macro_error]
+pub fn derive_value(input: TokenStream) -> TokenStream {
+    value::expand(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(to_stream)
+        .into()
+}
+
+/// Builds a `neo4jrs::Query`, checking at compile time that every `$placeholder` of the query is
+/// bound and every binding is used.
+///
+/// Parameters are bound with `name = value`, or `"first name" = value` for placeholders which
+/// aren't identifiers such as `` $`first name` ``. Placeholders which aren't bound capture the
+/// variable of the same name in scope, like `format!`, cloning it. Placeholders in strings,
+/// comments and quoted names, e.g. `` `$name` ``, are ignored.
+///
+/// ```ignore
+/// let name = "Alice";
+/// let query = cypher!(
+///     "MATCH (p:Person {name: $name}) WHERE p.age > $age RETURN p LIMIT $limit",
+///     age = user.age,
+///     limit = 10,
+/// );
+/// ```
+#[proc_macro]
+pub fn cypher(input: TokenStream) -> TokenStream {
+    cypher::expand(parse_macro_input!(input as cypher::Input))
+        .unwrap_or_else(to_stream)
+        .into()
+}
+
 fn to_stream(errors: Vec<syn::Error>) -> proc_macro2::TokenStream {
     let compile_errors = errors.iter().map(syn::Error::to_compile_error);
     quote!(#(#compile_errors)*)
@@ -0,0 +1,102 @@
+use crate::ast::{Container, Ctx, Data, Derive, Policy};
+use crate::label::{self, convert};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
+    let ctx = Ctx::new();
+
+    let cont = match Container::from_ast(&ctx, &ast, Derive::Properties) {
+        Some(cont) => cont,
+        None => return Err(ctx.check().unwrap_err()),
+    };
+
+    if cont.is_borrowed() {
+        let msg = "properties are read from the database, they can't borrow their fields";
+        ctx.error_spanned_by(&cont.original.generics, msg);
+    }
+    let Data::Struct(_, fields) = &cont.data;
+    for field in fields {
+        if field.attrs.relation().is_some()
+            || field.attrs.policy() != Policy::Always
+            || !field.attrs.schema().is_empty()
+            || field.attrs.managed().is_some()
+        {
+            let msg = "relation, on_create, on_match, constraints, indexes, versions and timestamps are not supported by #[derive(Properties)]";
+            ctx.error_spanned_by(field.original, msg);
+        }
+    }
+
+    ctx.check()?;
+
+    let injections = convert::injections(&cont, convert::Source::Properties);
+    let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let properties = fields
+        .iter()
+        .filter(|field| field.attrs.is_property())
+        .map(|field| {
+            let value = label::value(field);
+            let property = field.attrs.name();
+            quote!((neo4jrs::types::BoltString::from(#property), #value))
+        });
+    let flattened = label::flattened_fields(&cont)
+        .into_iter()
+        .map(label::flattened);
+
+    let expanded = quote! {
+        impl #impl_generics neo4jrs::Properties for #name #ty_generics #where_clause {
+            fn properties(&self) -> neo4jrs::types::BoltMap {
+                let properties: Vec<(neo4jrs::types::BoltString, neo4jrs::types::BoltType)> =
+                    vec![#(#properties),*];
+                properties.into_iter()#(.chain(#flattened))*.collect()
+            }
+
+            fn from_properties(
+                get: &dyn Fn(&str) -> Option<neo4jrs::types::BoltType>,
+            ) -> neo4jrs::Result<Self> {
+                Ok(Self { #(#injections)* })
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn should_read_and_write_the_properties_of_the_fields() {
+        let expanded = expand(parse_quote! {
+            #[neo4j(rename_all = "camelCase")]
+            struct Address {
+                street_name: String,
+                #[neo4j(serialize_with = "to_code", deserialize_with = "from_code")]
+                country: Country,
+                #[neo4j(flatten)]
+                location: Location,
+            }
+        })
+        .unwrap()
+        .to_string();
+
+        assert!(expanded.contains("impl neo4jrs :: Properties for Address"));
+        assert!(expanded.contains(r#"(neo4jrs :: types :: BoltString :: from ("streetName") , std :: convert :: Into :: < neo4jrs :: types :: BoltType > :: into (self . street_name . clone ()))"#));
+        assert!(expanded.contains(
+            r#"(neo4jrs :: types :: BoltString :: from ("country") , to_code (& self . country))"#
+        ));
+        assert!(expanded.contains(
+            ". chain (< Location as neo4jrs :: Properties > :: properties (& self . location))"
+        ));
+        assert!(expanded.contains(r#"street_name : match get ("streetName")"#));
+        assert!(expanded.contains("Some (value) => from_code (value) . map_err"));
+        assert!(expanded.contains(
+            "location : < Location as neo4jrs :: Properties > :: from_properties (get) ?"
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::ast::{Container, Ctx, Data, Derive, Policy};
+use crate::label::{self, convert};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
+    let ctx = Ctx::new();
+
+    let cont = match Container::from_ast(&ctx, &ast, Derive::Relation) {
+        Some(cont) => cont,
+        None => return Err(ctx.check().unwrap_err()),
+    };
+
+    let from = cont.attrs.from();
+    let to = cont.attrs.to();
+    if from.is_none() || to.is_none() {
+        let msg = "expected the nodes of the relationship, set them with #[neo4j(from = Type, to = Type)]";
+        ctx.error_spanned_by(cont.ident(), msg);
+    }
+
+    if cont.is_borrowed() {
+        let msg = "relationships are read from the database, they can't borrow their fields";
+        ctx.error_spanned_by(&cont.original.generics, msg);
+    }
+    let Data::Struct(_, fields) = &cont.data;
+    for field in fields {
+        if field.attrs.relation().is_some() {
+            let msg = "relation fields are only supported by #[derive(Label)]";
+            ctx.error_spanned_by(field.original, msg);
+        } else if !field.attrs.schema().is_empty() {
+            let msg = "constraints and indexes are only supported by #[derive(Label)]";
+            ctx.error_spanned_by(field.original, msg);
+        } else if field.attrs.managed().is_some() {
+            let msg = "versions and timestamps are only supported by #[derive(Label)]";
+            ctx.error_spanned_by(field.original, msg);
+        } else if field.attrs.policy() != Policy::Always {
+            let msg =
+                "relationship properties are always written, they can't be on_create or on_match";
+            ctx.error_spanned_by(field.original, msg);
+        }
+    }
+
+    ctx.check()?;
+
+    let (from, to) = match (from, to) {
+        (Some(from), Some(to)) => (from, to),
+        _ => return Ok(TokenStream::new()),
+    };
+    let injections = convert::injections(&cont, convert::Source::Entity);
+    let name = cont.ident();
+    let generics = cont.impl_generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let relationship_type = cont.attrs.relationship_type();
+    let properties = fields
+        .iter()
+        .filter(|field| field.attrs.is_property())
+        .map(|field| {
+            let value = label::value(field);
+            let property = field.attrs.name();
+            quote!((neo4jrs::types::BoltString::from(#property), #value))
+        });
+    let flattened = label::flattened_fields(&cont)
+        .into_iter()
+        .map(label::flattened);
+
+    let expanded = quote! {
+        impl #impl_generics neo4jrs::RelationshipEntity for #name #ty_generics #where_clause {
+            type From = #from;
+            type To = #to;
+
+            const TYPE: &'static str = #relationship_type;
+
+            fn properties(&self) -> neo4jrs::types::BoltMap {
+                let properties: Vec<(neo4jrs::types::BoltString, neo4jrs::types::BoltType)> =
+                    vec![#(#properties),*];
+                properties.into_iter()#(.chain(#flattened))*.collect()
+            }
+        }
+
+        impl #impl_generics std::convert::TryFrom<neo4jrs::Relation> for #name #ty_generics #where_clause {
+            type Error = neo4jrs::Error;
+
+            fn try_from(n: neo4jrs::Relation) -> neo4jrs::Result<Self> {
+                Ok(Self { #(#injections)* })
+            }
+        }
+
+        impl #impl_generics std::convert::TryFrom<neo4jrs::UnboundedRelation> for #name #ty_generics #where_clause {
+            type Error = neo4jrs::Error;
+
+            fn try_from(n: neo4jrs::UnboundedRelation) -> neo4jrs::Result<Self> {
+                Ok(Self { #(#injections)* })
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Creates this relationship from one node to the other, failing with
+            /// `Error::NoMatch` when either node doesn't exist
+            pub async fn connect(&self, from: &#from, to: &#to, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
+                let query = <Self as neo4jrs::RelationshipEntity>::connect_query(self, from, to);
+                match graph.execute(query).await?.next().await? {
+                    Some(_) => Ok(()),
+                    None => Err(neo4jrs::Error::NoMatch),
+                }
+            }
+
+            /// Deletes the relationships of this type from one node to the other
+            pub async fn disconnect(from: &#from, to: &#to, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
+                let query = <Self as neo4jrs::RelationshipEntity>::disconnect_query(from, to);
+                graph.run(query).await
+            }
+
+            /// Overwrites the properties of the relationships of this type from one node to the
+            /// other
+            pub async fn update(&self, from: &#from, to: &#to, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<()> {
+                let query = <Self as neo4jrs::RelationshipEntity>::update_query(self, from, to);
+                graph.run(query).await
+            }
+
+            /// The relationships of this type leaving the node, along with their end nodes
+            pub async fn outgoing(from: &#from, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Vec<(Self, #to)>> {
+                let query = <Self as neo4jrs::RelationshipEntity>::outgoing_query(from);
+                Self::pairs(query, graph)
+                    .await?
+                    .into_iter()
+                    .map(|(r, n)| Ok((r, <#to as std::convert::TryFrom<neo4jrs::Node>>::try_from(n)?)))
+                    .collect()
+            }
+
+            /// The relationships of this type reaching the node, along with their start nodes
+            pub async fn incoming(to: &#to, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Vec<(Self, #from)>> {
+                let query = <Self as neo4jrs::RelationshipEntity>::incoming_query(to);
+                Self::pairs(query, graph)
+                    .await?
+                    .into_iter()
+                    .map(|(r, n)| Ok((r, <#from as std::convert::TryFrom<neo4jrs::Node>>::try_from(n)?)))
+                    .collect()
+            }
+
+            async fn pairs(query: neo4jrs::Query, graph: &impl neo4jrs::Execute) -> neo4jrs::Result<Vec<(Self, neo4jrs::Node)>> {
+                let mut list = vec![];
+                let mut result = graph.execute(query).await?;
+
+                while let Some(row) = result.next().await? {
+                    if let (Some(r), Some(n)) = (row.get::<neo4jrs::Relation>("r"), row.get::<neo4jrs::Node>("n")) {
+                        list.push((
+                            <Self as std::convert::TryFrom<neo4jrs::Relation>>::try_from(r)?,
+                            n,
+                        ));
+                    }
+                }
+
+                Ok(list)
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_to_string(input: DeriveInput) -> String {
+        expand(input).unwrap().to_string()
+    }
+
+    fn expand_errors(input: DeriveInput) -> Vec<String> {
+        expand(input)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn should_name_the_type_after_the_struct_by_default() {
+        let expanded = expand_to_string(parse_quote! {
+            #[neo4j(from = Person, to = crate::models::Company)]
+            struct WorksAt {
+                since: i64,
+            }
+        });
+
+        assert!(expanded.contains(r#"const TYPE : & 'static str = "WORKS_AT" ;"#));
+        assert!(expanded.contains("type From = Person ;"));
+        assert!(expanded.contains("type To = crate :: models :: Company ;"));
+        assert!(expanded.contains(
+            "pub async fn outgoing (from : & Person , graph : & impl neo4jrs :: Execute) -> neo4jrs :: Result < Vec < (Self , crate :: models :: Company) >>"
+        ));
+        assert!(expanded.contains(
+            "pub async fn incoming (to : & crate :: models :: Company , graph : & impl neo4jrs :: Execute) -> neo4jrs :: Result < Vec < (Self , Person) >>"
+        ));
+    }
+
+    #[test]
+    fn should_use_the_field_attributes_of_nodes() {
+        let expanded = expand_to_string(parse_quote! {
+            #[neo4j(type = "FOLLOWS", from = Person, to = Person, rename_all = "camelCase")]
+            struct Follows {
+                followed_at: i64,
+                #[neo4j(rename = "via")]
+                source: Option<String>,
+                #[neo4j(skip)]
+                cache: Vec<String>,
+            }
+        });
+
+        assert!(expanded.contains(r#"const TYPE : & 'static str = "FOLLOWS" ;"#));
+        assert!(expanded.contains(r#"neo4jrs :: types :: BoltString :: from ("followedAt")"#));
+        assert!(expanded.contains(r#"neo4jrs :: types :: BoltString :: from ("via")"#));
+        assert!(!expanded.contains(r#"BoltString :: from ("cache")"#));
+        assert!(expanded.contains("cache : std :: default :: Default :: default ()"));
+        assert!(
+            expanded.contains("impl std :: convert :: TryFrom < neo4jrs :: Relation > for Follows")
+        );
+    }
+
+    #[test]
+    fn should_allow_relationships_without_properties() {
+        let expanded = expand_to_string(parse_quote! {
+            #[neo4j(from = "Person", to = "Person")]
+            struct Knows;
+        });
+
+        assert!(expanded.contains(r#""KNOWS""#));
+        assert!(expanded.contains("Ok (Self { })"));
+    }
+
+    #[test]
+    fn should_report_invalid_attributes() {
+        let errors = expand_errors(parse_quote! {
+            #[neo4j(label = "Follows", from = 1)]
+            struct Follows {
+                #[neo4j(on_create)]
+                since: i64,
+            }
+        });
+
+        assert_eq!(
+            errors,
+            vec![
+                "neo4j attribute `label` is not supported by #[derive(Relationship)]",
+                "expected the start node type",
+                "expected the nodes of the relationship, set them with #[neo4j(from = Type, to = Type)]",
+                "relationship properties are always written, they can't be on_create or on_match",
+            ]
+        );
+    }
+}
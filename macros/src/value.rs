@@ -0,0 +1,256 @@
+use crate::ast::{get_neo4j_meta_items, lit, Attr, Ctx, RenameRule, RENAME, RENAME_ALL};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Meta::NameValue;
+use syn::NestedMeta::Meta;
+use syn::{Data, DeriveInput, Fields};
+
+pub fn expand(ast: DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
+    let ctx = Ctx::new();
+    let expanded = match &ast.data {
+        Data::Enum(data) => unit_enum(&ctx, &ast, data),
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                newtype(&ast, &fields.unnamed[0].ty)
+            }
+            _ => {
+                let msg = "#[derive(Value)] supports enums of unit variants and newtype structs";
+                ctx.error_spanned_by(&ast.ident, msg);
+                TokenStream::new()
+            }
+        },
+        _ => {
+            let msg = "#[derive(Value)] supports enums of unit variants and newtype structs";
+            ctx.error_spanned_by(&ast.ident, msg);
+            TokenStream::new()
+        }
+    };
+    ctx.check()?;
+    Ok(expanded)
+}
+
+/// Stores the variants as strings, their names unless renamed
+fn unit_enum(cx: &Ctx, ast: &DeriveInput, data: &syn::DataEnum) -> TokenStream {
+    let name = &ast.ident;
+    let mut rename_all = Attr::none(cx, RENAME_ALL);
+    for item in ast
+        .attrs
+        .iter()
+        .flat_map(|attr| get_neo4j_meta_items(cx, attr))
+        .flatten()
+    {
+        match &item {
+            // Parse `#[neo4j(rename_all = "...")]`
+            Meta(NameValue(m)) if m.path == RENAME_ALL => match lit::to_string(&m.lit) {
+                Some(rule) => match RenameRule::from_str(&rule) {
+                    Ok(rule) => rename_all.set(&m.path, rule),
+                    Err(err) => cx.error_spanned_by(&m.lit, err),
+                },
+                None => cx.error_spanned_by(&m.lit, "expected the rename rule as a string"),
+            },
+            _ => cx.error_spanned_by(&item, "unexpected neo4j attribute on a Value enum"),
+        }
+    }
+    let rename_all = rename_all.get().unwrap_or(RenameRule::None);
+
+    let (variants, values): (Vec<&syn::Ident>, Vec<String>) = data
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            if !matches!(variant.fields, Fields::Unit) {
+                cx.error_spanned_by(variant, "#[derive(Value)] only supports unit variants");
+                return None;
+            }
+            let mut rename = Attr::none(cx, RENAME);
+            for item in variant
+                .attrs
+                .iter()
+                .flat_map(|attr| get_neo4j_meta_items(cx, attr))
+                .flatten()
+            {
+                match &item {
+                    // Parse `#[neo4j(rename = "...")]`
+                    Meta(NameValue(m)) if m.path == RENAME => match lit::to_string(&m.lit) {
+                        Some(value) => rename.set(&m.path, value),
+                        None => cx.error_spanned_by(&m.lit, "expected the new name as a string"),
+                    },
+                    _ => cx.error_spanned_by(&item, "unexpected neo4j attribute on a variant"),
+                }
+            }
+            let value = rename
+                .get()
+                .unwrap_or_else(|| rename_all.apply_to_variant(&variant.ident.to_string()));
+            Some((&variant.ident, value))
+        })
+        .unzip();
+    let target = name.to_string();
+    let expected = values
+        .iter()
+        .map(|value| format!("{:?}", value))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    quote! {
+        impl std::convert::From<#name> for neo4jrs::types::BoltType {
+            fn from(value: #name) -> Self {
+                let value = match value {
+                    #(#name::#variants => #values,)*
+                };
+                neo4jrs::types::BoltType::String(neo4jrs::types::BoltString::from(value))
+            }
+        }
+
+        impl std::convert::TryFrom<neo4jrs::types::BoltType> for #name {
+            type Error = neo4jrs::Error;
+
+            fn try_from(value: neo4jrs::types::BoltType) -> neo4jrs::Result<Self> {
+                match value {
+                    neo4jrs::types::BoltType::String(value) => match value.value.as_str() {
+                        #(#values => Ok(#name::#variants),)*
+                        other => Err(neo4jrs::Error::DeserializationError(format!(
+                            "unknown variant {:?} of `{}`, expected one of {}",
+                            other, #target, #expected
+                        ))),
+                    },
+                    other => Err(neo4jrs::Error::DeserializationError(format!(
+                        "expected `{}` as a string, got {:?}",
+                        #target, other
+                    ))),
+                }
+            }
+        }
+    }
+}
+
+/// Stores the wrapped value
+fn newtype(ast: &DeriveInput, inner: &syn::Type) -> TokenStream {
+    let name = &ast.ident;
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    let into = bounded(
+        ast,
+        &[quote!(#inner: std::convert::Into<neo4jrs::types::BoltType>)],
+    );
+    let (into_generics, _, into_where) = into.split_for_impl();
+    let try_from = bounded(
+        ast,
+        &[
+            quote!(#inner: std::convert::TryFrom<neo4jrs::types::BoltType>),
+            quote!(neo4jrs::Error: std::convert::From<<#inner as std::convert::TryFrom<neo4jrs::types::BoltType>>::Error>),
+        ],
+    );
+    let target = name.to_string();
+    let (try_from_generics, _, try_from_where) = try_from.split_for_impl();
+    quote! {
+        impl #into_generics std::convert::From<#name #ty_generics> for neo4jrs::types::BoltType #into_where {
+            fn from(value: #name #ty_generics) -> Self {
+                std::convert::Into::<neo4jrs::types::BoltType>::into(value.0)
+            }
+        }
+
+        impl #try_from_generics std::convert::TryFrom<neo4jrs::types::BoltType> for #name #ty_generics #try_from_where {
+            type Error = neo4jrs::Error;
+
+            fn try_from(value: neo4jrs::types::BoltType) -> neo4jrs::Result<Self> {
+                <#inner as std::convert::TryFrom<neo4jrs::types::BoltType>>::try_from(value)
+                    .map(#name)
+                    .map_err(|err| neo4jrs::Error::InvalidValue {
+                        target: #target.to_owned(),
+                        source: Box::new(neo4jrs::Error::from(err)),
+                    })
+            }
+        }
+    }
+}
+
+/// The generics of the struct, with the bounds when it is generic
+fn bounded(ast: &DeriveInput, bounds: &[TokenStream]) -> syn::Generics {
+    let mut generics = ast.generics.clone();
+    if generics.params.is_empty() {
+        return generics;
+    }
+    let predicates = &mut generics.make_where_clause().predicates;
+    for bound in bounds {
+        predicates.push(syn::parse_quote!(#bound));
+    }
+    generics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn should_store_unit_variants_as_strings() {
+        let expanded = expand(parse_quote! {
+            #[neo4j(rename_all = "SCREAMING_SNAKE_CASE")]
+            enum Status {
+                Active,
+                OnHold,
+                #[neo4j(rename = "gone")]
+                Deleted,
+            }
+        })
+        .unwrap()
+        .to_string();
+
+        assert!(expanded.contains(
+            r#"Status :: Active => "ACTIVE" , Status :: OnHold => "ON_HOLD" , Status :: Deleted => "gone" ,"#
+        ));
+        assert!(expanded.contains(
+            r#""ACTIVE" => Ok (Status :: Active) , "ON_HOLD" => Ok (Status :: OnHold) , "gone" => Ok (Status :: Deleted) ,"#
+        ));
+    }
+
+    #[test]
+    fn should_store_the_value_of_newtypes() {
+        let expanded = expand(parse_quote! {
+            struct Email(String);
+        })
+        .unwrap()
+        .to_string();
+
+        assert!(expanded
+            .contains("impl std :: convert :: From < Email > for neo4jrs :: types :: BoltType"));
+        assert!(expanded.contains(". map (Email)"));
+
+        let expanded = expand(parse_quote! {
+            struct Id<T>(T);
+        })
+        .unwrap()
+        .to_string();
+
+        assert!(expanded.contains(
+            "impl < T > std :: convert :: From < Id < T > > for neo4jrs :: types :: BoltType where T : std :: convert :: Into < neo4jrs :: types :: BoltType >"
+        ));
+        assert!(expanded.contains(
+            "impl < T > std :: convert :: TryFrom < neo4jrs :: types :: BoltType > for Id < T > where T : std :: convert :: TryFrom < neo4jrs :: types :: BoltType >"
+        ));
+    }
+
+    #[test]
+    fn should_report_unsupported_types() {
+        let errors = expand(parse_quote! {
+            enum Shape {
+                Circle(f64),
+                Empty,
+            }
+        })
+        .unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "#[derive(Value)] only supports unit variants"
+        );
+
+        let errors = expand(parse_quote! {
+            struct Point {
+                x: f64,
+            }
+        })
+        .unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "#[derive(Value)] supports enums of unit variants and newtype structs"
+        );
+    }
+}